@@ -1,80 +1,159 @@
+use advent_of_code_2023::variants::{variants, VARIANTS};
 use advent_of_code_2023::*;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 
-const INPUTS: &[&str] = &[
-    include_str!("../inputs/day1.txt"),
-    include_str!("../inputs/day2.txt"),
-    include_str!("../inputs/day3.txt"),
-    include_str!("../inputs/day4.txt"),
-    include_str!("../inputs/day5.txt"),
-    include_str!("../inputs/day6.txt"),
-    include_str!("../inputs/day7.txt"),
-    include_str!("../inputs/day8.txt"),
-    include_str!("../inputs/day9.txt"),
-    include_str!("../inputs/day10.txt"),
-    include_str!("../inputs/day11.txt"),
-    include_str!("../inputs/day12.txt"),
-    include_str!("../inputs/day13.txt"),
-    include_str!("../inputs/day14.txt"),
-    include_str!("../inputs/day15.txt"),
-    include_str!("../inputs/day16.txt"),
-    include_str!("../inputs/day17.txt"),
-    include_str!("../inputs/day18.txt"),
-    include_str!("../inputs/day19.txt"),
-    include_str!("../inputs/day20.txt"),
-    "", // outside of the library crate. see src/bin/day21.rs
-    include_str!("../inputs/day22.txt"),
-    include_str!("../inputs/day23.txt"),
-    include_str!("../inputs/day24.txt"),
-];
+// the days that only the binary runs. their tests come along but don't run
+#[path = "../src/bin/main/day21.rs"]
+#[allow(dead_code, unused_imports)]
+mod day21;
+#[path = "../src/bin/main/day25.rs"]
+#[allow(dead_code, unused_imports)]
+mod day25;
 
-fn benchmark(c: &mut Criterion) {
-    c.bench_function("day1part1", |b| b.iter(|| day1::part1(black_box(INPUTS[0]))));
-    c.bench_function("day1part2", |b| b.iter(|| day1::part2(black_box(INPUTS[0]))));
-    c.bench_function("day2part1", |b| b.iter(|| day2::part1(black_box(INPUTS[1]))));
-    c.bench_function("day2part2", |b| b.iter(|| day2::part2(black_box(INPUTS[1]))));
-    c.bench_function("day3part1", |b| b.iter(|| day3::part1(black_box(INPUTS[2]))));
-    c.bench_function("day3part2", |b| b.iter(|| day3::part2(black_box(INPUTS[2]))));
-    c.bench_function("day4part1", |b| b.iter(|| day4::part1(black_box(INPUTS[3]))));
-    c.bench_function("day4part2", |b| b.iter(|| day4::part2(black_box(INPUTS[3]))));
-    c.bench_function("day5part1", |b| b.iter(|| day5::part1(black_box(INPUTS[4]))));
-    c.bench_function("day5part2", |b| b.iter(|| day5::part2(black_box(INPUTS[4]))));
-    c.bench_function("day6part1", |b| b.iter(|| day6::part1(black_box(INPUTS[5]))));
-    c.bench_function("day6part2", |b| b.iter(|| day6::part2(black_box(INPUTS[5]))));
-    c.bench_function("day7part1", |b| b.iter(|| day7::part1(black_box(INPUTS[6]))));
-    c.bench_function("day7part2", |b| b.iter(|| day7::part2(black_box(INPUTS[6]))));
-    c.bench_function("day8part1", |b| b.iter(|| day8::part1(black_box(INPUTS[7]))));
-    c.bench_function("day8part2", |b| b.iter(|| day8::part2(black_box(INPUTS[7]))));
-    c.bench_function("day9part1", |b| b.iter(|| day9::part1(black_box(INPUTS[8]))));
-    c.bench_function("day9part2", |b| b.iter(|| day9::part2(black_box(INPUTS[8]))));
-    c.bench_function("day10part1", |b| b.iter(|| day10::part1(black_box(INPUTS[9]))));
-    c.bench_function("day10part2", |b| b.iter(|| day10::part2(black_box(INPUTS[9]))));
-    c.bench_function("day11part1", |b| b.iter(|| day11::part1(black_box(INPUTS[10]))));
-    c.bench_function("day11part2", |b| b.iter(|| day11::part2(black_box(INPUTS[10]))));
-    c.bench_function("day12part1", |b| b.iter(|| day12::part1(black_box(INPUTS[11]))));
-    c.bench_function("day12part2", |b| b.iter(|| day12::part2(black_box(INPUTS[11]))));
-    c.bench_function("day13part1", |b| b.iter(|| day13::part1(black_box(INPUTS[12]))));
-    c.bench_function("day13part2", |b| b.iter(|| day13::part2(black_box(INPUTS[12]))));
-    c.bench_function("day14part1", |b| b.iter(|| day14::part1(black_box(INPUTS[13]))));
-    c.bench_function("day14part2", |b| b.iter(|| day14::part2(black_box(INPUTS[13]))));
-    c.bench_function("day15part1", |b| b.iter(|| day15::part1(black_box(INPUTS[14]))));
-    c.bench_function("day15part2", |b| b.iter(|| day15::part2(black_box(INPUTS[14]))));
-    c.bench_function("day16part1", |b| b.iter(|| day16::part1(black_box(INPUTS[15]))));
-    c.bench_function("day16part2", |b| b.iter(|| day16::part2(black_box(INPUTS[15]))));
-    c.bench_function("day17part1", |b| b.iter(|| day17::part1(black_box(INPUTS[16]))));
-    c.bench_function("day17part2", |b| b.iter(|| day17::part2(black_box(INPUTS[16]))));
-    c.bench_function("day18part1", |b| b.iter(|| day18::part1(black_box(INPUTS[17]))));
-    c.bench_function("day18part2", |b| b.iter(|| day18::part2(black_box(INPUTS[17]))));
-    c.bench_function("day19part1", |b| b.iter(|| day19::part1(black_box(INPUTS[18]))));
-    c.bench_function("day19part2", |b| b.iter(|| day19::part2(black_box(INPUTS[18]))));
-    c.bench_function("day20part1", |b| b.iter(|| day20::part1(black_box(INPUTS[19]))));
-    c.bench_function("day20part2", |b| b.iter(|| day20::part2(black_box(INPUTS[19]))));
-    c.bench_function("day22part1", |b| b.iter(|| day22::part1(black_box(INPUTS[21]))));
-    c.bench_function("day22part2", |b| b.iter(|| day22::part2(black_box(INPUTS[21]))));
-    c.bench_function("day23part1", |b| b.iter(|| day23::part1(black_box(INPUTS[22]))));
-    c.bench_function("day23part2", |b| b.iter(|| day23::part2(black_box(INPUTS[22]))));
-    c.bench_function("day24part1", |b| b.iter(|| day24::part1(black_box(INPUTS[23]))));
+// discards day 24's part 2 script, leaving the time it takes to write it
+struct Sink;
+
+impl std::fmt::Write for Sink {
+    fn write_str(&mut self, _: &str) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+// every day gets a group named after it, timing `parse` on the day's input and
+// each part on what that parsed, with fewer samples for the days given a
+// number. the results stay put and only a reference goes through `black_box`:
+// returning day 11's grid by value crashes rustc in debug builds
+macro_rules! days {
+    ($($day:ident $(($samples:expr))? { $($name:ident: $f:expr),* $(,)? })*) => {
+        const INPUTS: &[(&str, &str)] = &[
+            $((stringify!($day), include_str!(concat!("../inputs/", stringify!($day), ".txt"))),)*
+        ];
+
+        fn benchmark(c: &mut Criterion) {
+            $(
+                let input = INPUTS.iter().find(|(day, _)| *day == stringify!($day)).unwrap().1;
+                let parsed = $day::parse(input);
+                let mut group = c.benchmark_group(stringify!($day));
+                $(group.sample_size($samples);)?
+                group.bench_function("parse", |b| {
+                    b.iter(|| {
+                        black_box(&$day::parse(black_box(input)));
+                    })
+                });
+                $(group.bench_function(stringify!($name), |b| {
+                    b.iter(|| {
+                        black_box(&($f)(black_box(&parsed)));
+                    })
+                });)*
+                group.finish();
+            )*
+        }
+    };
+}
+
+days! {
+    day1 { part1: day1::part1, part2: day1::part2 }
+    day2 { part1: day2::part1, part2: day2::part2 }
+    day3 { part1: day3::part1, part2: day3::part2 }
+    day4 { part1: day4::part1, part2: day4::part2 }
+    day5 { part1: day5::part1, part2: day5::part2 }
+    day6 { part1: day6::part1, part2: day6::part2 }
+    day7 { part1: day7::part1, part2: day7::part2 }
+    day8 { part1: day8::part1, part2: day8::part2 }
+    day9 { part1: day9::part1, part2: day9::part2 }
+    day10 { part1: day10::part1, part2: day10::part2 }
+    day11 { part1: day11::part1, part2: day11::part2 }
+    day12 { part1: day12::part1, part2: day12::part2 }
+    day13 { part1: day13::part1, part2: day13::part2 }
+    day14 { part1: day14::part1, part2: day14::part2 }
+    day15 { part1: day15::part1, part2: day15::part2 }
+    day16 { part1: day16::part1, part2: day16::part2 }
+    day17 { part1: day17::part1, part2: day17::part2 }
+    day18 { part1: day18::part1, part2: day18::part2 }
+    day19 { part1: day19::part1, part2: day19::part2 }
+    day20 { part1: day20::part1, part2: day20::part2 }
+    day21 {
+        part1: |garden| day21::part1_with(garden, &Default::default()),
+        part2: |garden| day21::part2_with(garden, &Default::default()),
+    }
+    day22 { part1: day22::part1, part2: day22::part2 }
+    day23 { part1: day23::part1, part2: day23::part2 }
+    // part 2 is solved by another program, so this only writes it
+    day24 { part1: day24::part1, part2script: |sys| day24::part2_script(sys, &mut Sink) }
+    // which takes seconds
+    day25 (10) { part1: day25::part1 }
+}
+
+// each part that has variants gets a group timing all of them on the same input
+fn benchmark_variants(c: &mut Criterion) {
+    let mut parts: Vec<(u8, u8)> = VARIANTS.iter().map(|v| (v.day, v.part)).collect();
+    parts.dedup();
+    for (day, part) in parts {
+        let input = INPUTS[day as usize - 1].1;
+        let mut group = c.benchmark_group(format!("day{}part{}variants", day, part));
+        for variant in variants(day, part) {
+            group.bench_function(variant.name, |b| b.iter(|| (variant.solve)(black_box(input))));
+        }
+        group.finish();
+    }
+    // day 21's are in the binary and take the steps, and the simulation only
+    // finishes for a few hundred
+    let params = day21::Params { part2_steps: 65 + 2 * 131, ..Default::default() };
+    let garden = day21::parse(INPUTS[20].1);
+    let mut group = c.benchmark_group("day21part2variants");
+    group.bench_function("extrapolation", |b| {
+        b.iter(|| day21::part2_with(black_box(&garden), &params))
+    });
+    group.bench_function("simulation", |b| {
+        b.iter(|| day21::part2_simulated(black_box(&garden), &params))
+    });
+    group.finish();
+}
+
+// a group per day with a benchmark per size of `parse` and each part on what
+// it parsed, on generated inputs of each size, to see how they scale.
+// `benchreport --slopes` fits how the time grows with the size
+macro_rules! scaling {
+    ($($day:ident [$($size:expr),*] { $($name:ident: $f:expr),* $(,)? })*) => {
+        fn benchmark_scaling(c: &mut Criterion) {
+            $(
+                let num = stringify!($day).trim_start_matches("day").parse().unwrap();
+                let generator = gen::find(num).unwrap();
+                let mut group = c.benchmark_group(concat!(stringify!($day), "scaling"));
+                group.sample_size(10);
+                for size in [$($size),*] {
+                    let mut input = String::new();
+                    (generator.generate)(&mut gen::Rng::new(size as u64), size, &mut input)
+                        .unwrap();
+                    let parsed = $day::parse(&input);
+                    group.bench_with_input(BenchmarkId::new("parse", size), &input, |b, input| {
+                        b.iter(|| {
+                            black_box(&$day::parse(black_box(input)));
+                        })
+                    });
+                    $(group.bench_function(BenchmarkId::new(stringify!($name), size), |b| {
+                        b.iter(|| {
+                            black_box(&($f)(black_box(&parsed)));
+                        })
+                    });)*
+                }
+                group.finish();
+            )*
+        }
+    };
+}
+
+scaling! {
+    day11 [250, 500, 1000, 2000] { part1: day11::part1, part2: day11::part2 }
+    day12 [250, 500, 1000] { part1: day12::part1, part2: day12::part2 }
+    day14 [16, 32, 64, 128] { part1: day14::part1, part2: day14::part2 }
+    day16 [14, 28, 55, 110] { part1: day16::part1, part2: day16::part2 }
+    day17 [18, 35, 71, 141] { part1: day17::part1, part2: day17::part2 }
+    day20 [6, 8, 10, 12] { part1: day20::part1, part2: day20::part2 }
+    day22 [256, 512, 1024, 2048] { part1: day22::part1, part2: day22::part2 }
+    day23 [3, 4, 5, 6] { part1: day23::part1, part2: day23::part2 }
+    day24 [64, 128, 256, 512] { part1: day24::part1 }
 }
 
-criterion_group!(benches, benchmark);
+criterion_group!(benches, benchmark, benchmark_variants, benchmark_scaling);
 criterion_main!(benches);
@@ -0,0 +1,189 @@
+// just enough json to read the files criterion saves
+
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), usize> {
+        if self.bytes[self.pos..].starts_with(s.as_bytes()) {
+            self.pos += s.len();
+            Ok(())
+        } else {
+            Err(self.pos)
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, usize> {
+        self.skip_whitespace();
+        let value = match self.bytes.get(self.pos).ok_or(self.pos)? {
+            b'n' => self.expect("null").map(|_| Value::Null)?,
+            b't' => self.expect("true").map(|_| Value::Bool(true))?,
+            b'f' => self.expect("false").map(|_| Value::Bool(false))?,
+            b'"' => Value::String(self.string()?),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.close(b']') {
+                    loop {
+                        items.push(self.value()?);
+                        if self.close(b']') {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Value::Array(items)
+            }
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if !self.close(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.skip_whitespace();
+                        self.expect(":")?;
+                        fields.push((key, self.value()?));
+                        if self.close(b'}') {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Value::Object(fields)
+            }
+            _ => self.number()?,
+        };
+        self.skip_whitespace();
+        Ok(value)
+    }
+
+    // skips `close` if it's next
+    fn close(&mut self, close: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.bytes.get(self.pos) == Some(&close);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn string(&mut self) -> Result<String, usize> {
+        self.expect("\"")?;
+        let mut s = String::new();
+        loop {
+            let c = self.rest().chars().next().ok_or(self.pos)?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escape = *self.bytes.get(self.pos).ok_or(self.pos)?;
+                    self.pos += 1;
+                    s.push(match escape {
+                        b'n' => '\n',
+                        b't' => '\t',
+                        b'r' => '\r',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'u' => {
+                            let hex = self.rest().get(..4).ok_or(self.pos)?;
+                            let code = u32::from_str_radix(hex, 16).map_err(|_| self.pos)?;
+                            self.pos += 4;
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        b => b as char,
+                    });
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, usize> {
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| b"+-.eE0123456789".contains(b)) {
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap();
+        text.parse().map(Value::Number).map_err(|_| start)
+    }
+
+    fn rest(&self) -> &str {
+        std::str::from_utf8(&self.bytes[self.pos..]).unwrap_or_default()
+    }
+}
+
+// the byte offset of the first thing that doesn't parse, if any
+pub fn parse(text: &str) -> Result<Value, usize> {
+    let mut parser = Parser { bytes: text.as_bytes(), pos: 0 };
+    let value = parser.value()?;
+    if parser.pos < text.len() {
+        return Err(parser.pos);
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let value = parse(r#" {"a": [1, -2.5e3, true, null], "b": {"c": "d\"é"}, "e": []} "#);
+        let value = value.unwrap();
+        assert_eq!(
+            value.get("a"),
+            Some(&Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(-2500.0),
+                Value::Bool(true),
+                Value::Null
+            ]))
+        );
+        assert_eq!(value.get("b").and_then(|b| b.get("c")).and_then(Value::as_str), Some("d\"é"));
+        assert_eq!(value.get("e"), Some(&Value::Array(vec![])));
+        assert_eq!(parse("{\"a\": 1,}"), Err(8));
+        assert_eq!(parse("[1] 2"), Err(4));
+    }
+}
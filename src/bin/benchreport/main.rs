@@ -0,0 +1,131 @@
+mod json;
+mod report;
+
+use report::Row;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(
+        "usage: benchreport [--criterion DIR] [--current NAME] [--baseline NAME | none] [--threshold PERCENT] [--slopes]"
+    )]
+    Usage,
+    #[error("{0}: bad json at byte {1}")]
+    BadJson(PathBuf, usize),
+    #[error("{0}: no estimate")]
+    NoEstimate(PathBuf),
+    #[error("no benchmarks saved as {0} in {1}")]
+    NoBenchmarks(String, PathBuf),
+    #[error("{0}")]
+    IO(#[from] std::io::Error),
+}
+
+fn die(err: impl Into<Error>) -> ! {
+    std::eprintln!("{}", err.into());
+    std::process::exit(1);
+}
+
+fn read_json(path: &Path) -> Result<json::Value, Error> {
+    let text = std::fs::read_to_string(path)?;
+    json::parse(&text).map_err(|pos| Error::BadJson(path.to_path_buf(), pos))
+}
+
+fn read_estimate(path: &Path) -> Result<f64, Error> {
+    report::estimate(&read_json(path)?).ok_or_else(|| Error::NoEstimate(path.to_path_buf()))
+}
+
+enum Baseline {
+    // what criterion compared the last run to, which it saved as a change
+    Previous,
+    Named(String),
+    None,
+}
+
+// criterion keeps each benchmark in a directory of its own (nested under its
+// group's, if it has one), holding a directory per run it has saved: `new`
+// for the latest and any named with `--save-baseline`, which includes `base`
+// unless told otherwise. that's overwritten with the latest as soon as it's
+// been compared against, so the previous run only survives in `change`
+fn find(dir: &Path, current: &str, baseline: &Baseline, rows: &mut Vec<Row>) -> Result<(), Error> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let run = path.join(current);
+        if !run.join("estimates.json").exists() {
+            find(&path, current, baseline, rows)?;
+            continue;
+        }
+        let benchmark = run.join("benchmark.json");
+        let id = read_json(&benchmark)?.get("full_id").and_then(|id| id.as_str()).map(String::from);
+        let id = id.ok_or(Error::BadJson(benchmark, 0))?;
+        let current = read_estimate(&run.join("estimates.json"))?;
+        let path = match baseline {
+            Baseline::Previous => path.join("change").join("estimates.json"),
+            Baseline::Named(name) => path.join(name).join("estimates.json"),
+            Baseline::None => PathBuf::new(),
+        };
+        let baseline = match baseline {
+            _ if !path.is_file() => None,
+            Baseline::Previous => Some(current / (1.0 + read_estimate(&path)?)),
+            _ => Some(read_estimate(&path)?),
+        };
+        rows.push(Row { id, current, baseline });
+    }
+    Ok(())
+}
+
+struct Options {
+    criterion: PathBuf,
+    current: String,
+    baseline: Baseline,
+    // as a fraction
+    threshold: f64,
+    // fit how the times grow with the input size instead
+    slopes: bool,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, Error> {
+    let mut opts = Options {
+        criterion: PathBuf::from("target/criterion"),
+        current: "new".to_string(),
+        baseline: Baseline::Previous,
+        threshold: 0.05,
+        slopes: false,
+    };
+    while let Some(arg) = args.next() {
+        if arg == "--slopes" {
+            opts.slopes = true;
+            continue;
+        }
+        let value = args.next().ok_or(Error::Usage)?;
+        match arg.as_str() {
+            "--criterion" => opts.criterion = PathBuf::from(value),
+            "--current" => opts.current = value,
+            "--baseline" if value == "none" => opts.baseline = Baseline::None,
+            "--baseline" => opts.baseline = Baseline::Named(value),
+            "--threshold" => {
+                opts.threshold = value.parse::<f64>().map_err(|_| Error::Usage)? / 100.0
+            }
+            _ => return Err(Error::Usage),
+        }
+    }
+    Ok(opts)
+}
+
+fn main() {
+    let opts = parse_options(std::env::args().skip(1)).unwrap_or_else(|err| die(err));
+    let mut rows = Vec::new();
+    find(&opts.criterion, &opts.current, &opts.baseline, &mut rows).unwrap_or_else(|err| die(err));
+    if rows.is_empty() {
+        die(Error::NoBenchmarks(opts.current, opts.criterion));
+    }
+    rows.sort_by(|a, b| report::natural_key(&a.id).cmp(&report::natural_key(&b.id)));
+    if opts.slopes {
+        print!("{}", report::slopes_markdown(&rows));
+    } else {
+        print!("{}", report::markdown(&rows, opts.threshold));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::json::Value;
+use std::fmt::Write as _;
+
+// the mean time per iteration in nanoseconds, or for the `change` criterion
+// saves, the mean's change as a fraction of the baseline's
+pub fn estimate(estimates: &Value) -> Option<f64> {
+    estimates.get("mean")?.get("point_estimate")?.as_f64()
+}
+
+pub struct Row {
+    pub id: String,
+    pub current: f64,
+    pub baseline: Option<f64>,
+}
+
+impl Row {
+    // the change from the baseline, as a fraction of it
+    fn change(&self) -> Option<f64> {
+        self.baseline.map(|baseline| self.current / baseline - 1.0)
+    }
+}
+
+// splits the runs of digits from the rest, so that sorting by it puts day2
+// before day10
+pub fn natural_key(id: &str) -> Vec<(&str, u64)> {
+    let mut key = Vec::new();
+    let mut rest = id;
+    while !rest.is_empty() {
+        let (text, tail) =
+            rest.split_at(rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len()));
+        let (num, tail) =
+            tail.split_at(tail.find(|c: char| !c.is_ascii_digit()).unwrap_or(tail.len()));
+        key.push((text, num.parse().unwrap_or(0)));
+        rest = tail;
+    }
+    key
+}
+
+// like criterion: four or five significant digits and the largest unit that
+// keeps the number at least one
+pub fn format_time(ns: f64) -> String {
+    let (value, unit) = match ns {
+        ns if ns < 1e3 => (ns, "ns"),
+        ns if ns < 1e6 => (ns / 1e3, "µs"),
+        ns if ns < 1e9 => (ns / 1e6, "ms"),
+        ns => (ns / 1e9, "s"),
+    };
+    let precision = match value {
+        value if value < 10.0 => 4,
+        value if value < 100.0 => 3,
+        value if value < 1000.0 => 2,
+        _ => 1,
+    };
+    format!("{:.*} {}", precision, value, unit)
+}
+
+// a row per benchmark with its time, and if any has a baseline, the
+// baseline's time and the change, flagging changes beyond `threshold` (a
+// fraction) either way
+pub fn markdown(rows: &[Row], threshold: f64) -> String {
+    let compare = rows.iter().any(|row| row.baseline.is_some());
+    let mut out = String::new();
+    if compare {
+        out.push_str("| benchmark | time | baseline | change | |\n");
+        out.push_str("| --- | ---: | ---: | ---: | --- |\n");
+    } else {
+        out.push_str("| benchmark | time |\n");
+        out.push_str("| --- | ---: |\n");
+    }
+    for row in rows {
+        let time = format_time(row.current);
+        if !compare {
+            writeln!(out, "| {} | {} |", row.id, time).unwrap();
+            continue;
+        }
+        let (baseline, change, flag) = match (row.baseline, row.change()) {
+            (Some(baseline), Some(change)) => {
+                let flag = match change {
+                    change if change > threshold => "**regressed**",
+                    change if change < -threshold => "improved",
+                    _ => "",
+                };
+                (format_time(baseline), format!("{:+.1}%", change * 100.0), flag)
+            }
+            _ => ("-".to_string(), "-".to_string(), "new"),
+        };
+        writeln!(out, "| {} | {} | {} | {} | {} |", row.id, time, baseline, change, flag).unwrap();
+    }
+    out
+}
+
+// least squares fit of log(time) against log(size) for each benchmark that
+// was run at several sizes (ids ending in `/SIZE`), in order of first
+// appearance. the slope is the exponent k in time ~ size^k
+pub fn slopes(rows: &[Row]) -> Vec<(&str, Vec<u64>, f64)> {
+    let mut fits: Vec<(&str, Vec<(f64, f64)>)> = Vec::new();
+    for row in rows {
+        let Some((id, size)) = row.id.rsplit_once('/') else { continue };
+        let Ok(size) = size.parse::<u64>() else { continue };
+        let point = ((size as f64).ln(), row.current.ln());
+        match fits.iter_mut().find(|(other, _)| *other == id) {
+            Some((_, points)) => points.push(point),
+            None => fits.push((id, vec![point])),
+        }
+    }
+    fits.into_iter()
+        .filter(|(_, points)| points.len() > 1)
+        .map(|(id, points)| {
+            let n = points.len() as f64;
+            let (mx, my) = points.iter().fold((0.0, 0.0), |(x, y), p| (x + p.0 / n, y + p.1 / n));
+            let sxy: f64 = points.iter().map(|(x, y)| (x - mx) * (y - my)).sum();
+            let sxx: f64 = points.iter().map(|(x, _)| (x - mx) * (x - mx)).sum();
+            let sizes = points.iter().map(|(x, _)| x.exp().round() as u64).collect();
+            (id, sizes, sxy / sxx)
+        })
+        .collect()
+}
+
+pub fn slopes_markdown(rows: &[Row]) -> String {
+    let mut out = String::from("| benchmark | sizes | slope |\n| --- | --- | ---: |\n");
+    for (id, sizes, slope) in slopes(rows) {
+        let sizes: Vec<String> = sizes.iter().map(u64::to_string).collect();
+        writeln!(out, "| {} | {} | {:.2} |", id, sizes.join(", "), slope).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::json;
+
+    #[test]
+    fn test_estimate() {
+        let text = r#"{"mean": {"point_estimate": 2.0}, "slope": {"point_estimate": 2.5}}"#;
+        assert_eq!(estimate(&json::parse(text).unwrap()), Some(2.0));
+        assert_eq!(estimate(&json::parse(r#"{"slope": null}"#).unwrap()), None);
+    }
+
+    #[test]
+    fn test_slopes() {
+        let row = |id: &str, current| Row { id: id.into(), current, baseline: None };
+        let rows = [
+            row("day11/part1", 5.0),
+            row("day11scaling/part1/10", 300.0),
+            row("day11scaling/part1/20", 1200.0),
+            row("day11scaling/part1/40", 4800.0),
+            row("day24scaling/part1/64", 7.0),
+        ];
+        assert_eq!(
+            slopes_markdown(&rows),
+            "| benchmark | sizes | slope |\n| --- | --- | ---: |\n| day11scaling/part1 | 10, 20, 40 | 2.00 |\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(format_time(170.84), "170.84 ns");
+        assert_eq!(format_time(27823.0), "27.823 µs");
+        assert_eq!(format_time(2890300.0), "2.8903 ms");
+        assert_eq!(format_time(2947400000.0), "2.9474 s");
+
+        let mut ids = ["day10/part1", "day2/part2", "day2/parse", "day1/part1"];
+        ids.sort_by_key(|id| natural_key(id));
+        assert_eq!(ids, ["day1/part1", "day2/parse", "day2/part2", "day10/part1"]);
+
+        let rows = [
+            Row { id: "day1/part1".into(), current: 27823.0, baseline: Some(27000.0) },
+            Row { id: "day1/part2".into(), current: 80000.0, baseline: Some(67000.0) },
+            Row { id: "day2/part1".into(), current: 20000.0, baseline: Some(27000.0) },
+            Row { id: "day2/part2".into(), current: 40000.0, baseline: None },
+        ];
+        assert_eq!(
+            markdown(&rows, 0.05),
+            "| benchmark | time | baseline | change | |
+| --- | ---: | ---: | ---: | --- |
+| day1/part1 | 27.823 µs | 27.000 µs | +3.0% |  |
+| day1/part2 | 80.000 µs | 67.000 µs | +19.4% | **regressed** |
+| day2/part1 | 20.000 µs | 27.000 µs | -25.9% | improved |
+| day2/part2 | 40.000 µs | - | - | new |
+"
+        );
+        assert_eq!(
+            markdown(&rows[3..], 0.05),
+            "| benchmark | time |\n| --- | ---: |\n| day2/part2 | 40.000 µs |\n"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+// answers.toml has a `[dayN]` section per day with a `partM = answer` line for
+// each part whose answer is known. anything after a `#` is a comment.
+pub type Answers<'a> = BTreeMap<(&'a str, u8), &'a str>;
+
+#[derive(Debug, PartialEq)]
+pub enum Status<'a> {
+    Pass,
+    Fail { expected: &'a str },
+    Missing,
+    // the part's answer comes out of another program
+    Skip,
+}
+
+// the line number of the first line that doesn't parse, if any
+pub fn parse(text: &str) -> Result<Answers<'_>, usize> {
+    let mut answers = Answers::new();
+    let mut day = None;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            day = Some(section.trim());
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or(i + 1)?;
+        let part = key.trim().strip_prefix("part").and_then(|part| part.parse().ok());
+        match (day, part, value.trim().trim_matches('"')) {
+            (Some(day), Some(part @ (1 | 2)), value) if !value.is_empty() => {
+                answers.insert((day, part), value);
+            }
+            _ => return Err(i + 1),
+        }
+    }
+    Ok(answers)
+}
+
+pub fn check<'a>(answers: &Answers<'a>, day: &str, part: u8, got: &str) -> Status<'a> {
+    match answers.get(&(day, part)) {
+        Some(expected) if *expected == got => Status::Pass,
+        Some(expected) => Status::Fail { expected },
+        None => Status::Missing,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "# answers\n[day1]\npart1 = 142 # the example\npart2 = \"281\"\n\n[day2]\n";
+        let answers = parse(text).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(check(&answers, "day1", 1, "142"), Status::Pass);
+        assert_eq!(check(&answers, "day1", 2, "280"), Status::Fail { expected: "281" });
+        assert_eq!(check(&answers, "day2", 1, "8"), Status::Missing);
+        assert_eq!(parse("part1 = 3"), Err(1));
+        assert_eq!(parse("[day1]\npart3 = 3"), Err(2));
+    }
+}
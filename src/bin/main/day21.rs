@@ -0,0 +1,188 @@
+use advent_of_code_2023::params::{self, Error};
+use std::collections::VecDeque;
+
+type Pt = (i16, i16);
+type Grid = Vec<Vec<Plot>>;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Plot {
+    Garden,
+    Rock,
+}
+
+fn get(grid: &Grid, (r, c): Pt) -> Plot {
+    grid[r.rem_euclid(grid.len() as i16) as usize][c.rem_euclid(grid[0].len() as i16) as usize]
+}
+
+fn nbrs((r, c): Pt) -> [Pt; 4] {
+    [(r + 1, c), (r - 1, c), (r, c + 1), (r, c - 1)]
+}
+
+// a plot can be reached in exactly `max` steps if it can be reached in fewer
+// by an even number, since the last two can go back and forth, so this counts
+// them in a breadth first search out to `max`
+fn explore(grid: &Grid, start: Pt, max: i16) -> i64 {
+    let side = 2 * max as usize + 1;
+    let index = |(r, c): Pt| (r - start.0 + max) as usize * side + (c - start.1 + max) as usize;
+    let mut seen = vec![false; side * side];
+    seen[index(start)] = true;
+    let mut q = VecDeque::from([(start, 0)]);
+    let mut count = 0;
+    while let Some((pt, steps)) = q.pop_front() {
+        if (max - steps) % 2 == 0 {
+            count += 1;
+        }
+        if steps == max {
+            continue;
+        }
+        for nbr in nbrs(pt).into_iter().filter(|nbr| get(grid, *nbr) != Plot::Rock) {
+            if !seen[index(nbr)] {
+                seen[index(nbr)] = true;
+                q.push_back((nbr, steps + 1));
+            }
+        }
+    }
+    count
+}
+
+// the plots, and where the elf starts
+pub struct Garden {
+    grid: Grid,
+    start: Pt,
+}
+
+pub fn parse(input: &str) -> Garden {
+    let mut start = (0, 0);
+    let mut grid = Grid::new();
+    for (r, line) in input.lines().enumerate() {
+        let mut row = Vec::new();
+        for (c, b) in line.bytes().enumerate() {
+            row.push(match b {
+                b'.' => Plot::Garden,
+                b'#' => Plot::Rock,
+                b'S' => {
+                    start = (r as i16, c as i16);
+                    Plot::Garden
+                }
+                _ => unreachable!(),
+            });
+        }
+        grid.push(row);
+    }
+    Garden { grid, start }
+}
+
+// how many steps the elf takes in each part
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub part1_steps: i16,
+    pub part2_steps: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { part1_steps: 64, part2_steps: 26501365 }
+    }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["part1_steps", "part2_steps"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "part1_steps" => self.part1_steps = params::parse(value)?,
+            "part2_steps" => self.part2_steps = params::parse(value)?,
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
+    }
+}
+
+// the cli's only entry points, since it always has the parameters
+pub fn part1_with(garden: &Garden, params: &Params) -> i64 {
+    explore(&garden.grid, garden.start, params.part1_steps)
+}
+
+// the fit in part 2 only holds for steps that end at the edge of a copy of
+// the grid, which the cli checks once it has the input
+pub fn check(garden: &Garden, params: &Params) -> Result<(), Error> {
+    let (size, half) = (garden.grid.len() as i64, garden.start.0 as i64);
+    let steps = params.part2_steps;
+    if steps < half || (steps - half) % size != 0 {
+        return Err(Error::Invalid);
+    }
+    Ok(())
+}
+
+pub fn part2_with(garden: &Garden, params: &Params) -> i64 {
+    let Garden { grid, start } = garden;
+    let start = *start;
+    let (size, half) = (grid.len() as i16, start.0);
+    assert_eq!(grid[0].len() as i16, size, "the grid is not square");
+
+    // the row and column of the start is empty:
+    assert!(grid[start.0 as usize].iter().all(|plot| *plot == Plot::Garden));
+    assert!(grid.iter().all(|row| row[start.1 as usize] == Plot::Garden));
+
+    // printing the grid at each step shows that it follows a diamond
+    // pattern. a diamond is a fancy square, so going twice as far will
+    // produce roughly 4x as many points. fit a quadratic equation:
+
+    // find the points to interpolate
+    let (y1, y2, y3) = (
+        explore(grid, start, half),
+        explore(grid, start, half + size),
+        explore(grid, start, half + size * 2),
+    );
+
+    // https://www.geeksforgeeks.org/lagrange-interpolation-formula/
+    // the sums are even, though y1 and y3 may not be
+    let a = (y1 - 2 * y2 + y3) / 2;
+    let b = (-3 * y1 + 4 * y2 - y3) / 2;
+    let c = y1;
+    let x = (params.part2_steps - half as i64) / size as i64;
+    let y = a * x * x + b * x + c;
+
+    // shout out to reddit
+    y
+}
+
+// what the fit extrapolates, found by searching all the way out instead,
+// which only finishes for steps in the hundreds
+pub fn part2_simulated(garden: &Garden, params: &Params) -> i64 {
+    let steps = i16::try_from(params.part2_steps).expect("too many steps to simulate");
+    explore(&garden.grid, garden.start, steps)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+        let garden = parse(input);
+        let (grid, start) = (&garden.grid, garden.start);
+        assert_eq!(explore(grid, start, 6), 16);
+        assert_eq!(part1_with(&garden, &Params { part1_steps: 6, ..Params::default() }), 16);
+        assert_eq!(explore(grid, start, 10), 50);
+        assert_eq!(explore(grid, start, 50), 1594);
+        assert_eq!(explore(grid, start, 100), 6536);
+
+        for (part2_steps, ok) in [(5 + 11 * 4, true), (100, false), (4, false), (26501365, false)] {
+            let params = Params { part2_steps, ..Params::default() };
+            assert_eq!(check(&garden, &params).is_ok(), ok, "{}", part2_steps);
+        }
+    }
+}
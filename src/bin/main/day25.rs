@@ -1,10 +1,10 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct Node<'a>(BTreeSet<&'a str>);
 
 impl<'a> Node<'a> {
-    fn new(key: &'a str) -> Node {
+    fn new(key: &'a str) -> Node<'a> {
         Self(BTreeSet::from_iter(std::iter::once(key)))
     }
 
@@ -22,7 +22,7 @@ impl<'a> Node<'a> {
 type WeightedEdges<'a> = BTreeMap<Node<'a>, i64>;
 
 #[derive(Clone)]
-struct WeightedGraph<'a>(BTreeMap<Node<'a>, WeightedEdges<'a>>);
+pub struct WeightedGraph<'a>(BTreeMap<Node<'a>, WeightedEdges<'a>>);
 
 impl<'a> WeightedGraph<'a> {
     fn new() -> Self {
@@ -43,11 +43,11 @@ impl<'a> WeightedGraph<'a> {
     }
 
     fn remove(&mut self, node: &Node<'a>) {
-        let nbrs = self.0.get(&node).unwrap().keys().cloned().collect::<Vec<_>>();
+        let nbrs = self.0.get(node).unwrap().keys().cloned().collect::<Vec<_>>();
         for dst in nbrs {
-            self.0.get_mut(&dst).unwrap().remove(&node);
+            self.0.get_mut(&dst).unwrap().remove(node);
         }
-        self.0.remove(&node);
+        self.0.remove(node);
     }
 
     fn keys(&self) -> impl Iterator<Item = &Node<'a>> {
@@ -55,37 +55,16 @@ impl<'a> WeightedGraph<'a> {
     }
 }
 
-fn print_graph(graph: &WeightedGraph) {
-    for (src, dsts) in graph.0.iter() {
-        println!("{:?} -> {:?}", src, dsts);
-    }
-}
-
 type Nodes<'a, 'b> = BTreeSet<&'b Node<'a>>;
 
-fn weight(from: &Node, to: &Nodes, graph: &WeightedGraph) -> i64 {
-    to.iter().map(|to| graph.get(from).unwrap().get(to).unwrap_or(&0)).sum()
-}
-
-fn most_tightly_connected<'c, 'b: 'c, 'a: 'b>(
-    graph: &'b WeightedGraph<'a>,
-    not_in: &'c Nodes<'a, 'b>,
-) -> &'b Node<'a> {
-    graph
-        .keys()
-        .filter(|node| !not_in.contains(node))
-        .max_by_key(|node| weight(node, not_in, graph))
-        .unwrap()
-}
-
 fn merge<'a>(graph: &mut WeightedGraph<'a>, node1: &Node<'a>, node2: &Node<'a>) {
-    let merged = node1.merge_with(&node2);
-    for (dst, weight) in graph.get(&node1).unwrap().clone() {
+    let merged = node1.merge_with(node2);
+    for (dst, weight) in graph.get(node1).unwrap().clone() {
         if &dst != node2 {
             graph.add_edge(merged.clone(), dst.clone(), weight);
         }
     }
-    for (dst, weight) in graph.get(&node2).unwrap().clone() {
+    for (dst, weight) in graph.get(node2).unwrap().clone() {
         if &dst != node1 {
             graph.add_edge(merged.clone(), dst.clone(), weight);
         }
@@ -98,17 +77,30 @@ type NodeList<'a> = Vec<Node<'a>>;
 type Cut<'a> = (i64, NodeList<'a>);
 
 // stoer-wagner algorithm: https://dl.acm.org/doi/pdf/10.1145/263867.263872
+// each node outside `a` keeps how tightly it's connected to it, which goes up
+// as its neighbors join, in a heap whose stale entries are skipped
 fn min_cut_phase<'b, 'a: 'b>(graph: &'b mut WeightedGraph<'a>, src: &Node<'a>) -> Cut<'a> {
-    println!("cutting");
     let mut a = Nodes::from_iter(std::iter::once(src));
-    let (mut prev2, mut prev1) = (None, src);
+    let mut connected: BTreeMap<&Node<'a>, i64> = BTreeMap::new();
+    let mut heap = BinaryHeap::new();
+    let (mut prev2, mut prev1, mut cut) = (None, src, 0);
     while a.len() < graph.len() {
-        let next = most_tightly_connected(graph, &a);
-        println!("most tightly connected: {:?}", next);
+        for (dst, weight) in graph.get(prev1).unwrap() {
+            if !a.contains(dst) {
+                let total = connected.entry(dst).or_default();
+                *total += weight;
+                heap.push((*total, dst));
+            }
+        }
+        let (weight, next) = loop {
+            let (weight, node) = heap.pop().unwrap();
+            if !a.contains(node) && connected[node] == weight {
+                break (weight, node);
+            }
+        };
         a.insert(next);
-        (prev2, prev1) = (Some(prev1), next);
+        (prev2, prev1, cut) = (Some(prev1), next, weight);
     }
-    let cut = weight(prev1, &a, graph);
     let (prev2, prev1) = (prev2.unwrap().clone(), prev1.clone());
     let a = a.into_iter().cloned().collect();
     merge(graph, &prev2, &prev1);
@@ -116,22 +108,19 @@ fn min_cut_phase<'b, 'a: 'b>(graph: &'b mut WeightedGraph<'a>, src: &Node<'a>) -
 }
 
 fn partition<'a>(mut graph: WeightedGraph<'a>) -> Node<'a> {
-    print_graph(&graph);
     let src = graph.keys().next().unwrap().clone();
     let mut min_cut = min_cut_phase(&mut graph, &src);
     while graph.len() > 2 {
-        println!("tick");
         let cut = min_cut_phase(&mut graph, &src);
         if cut.0 < min_cut.0 {
             min_cut = cut;
         }
-        print_graph(&graph);
     }
     // extract the cluster
-    min_cut.1.into_iter().filter(|node| node.len() > 1).next().unwrap()
+    min_cut.1.into_iter().find(|node| node.len() > 1).unwrap()
 }
 
-fn parse<'a>(input: &'a str) -> WeightedGraph<'a> {
+pub fn parse<'a>(input: &'a str) -> WeightedGraph<'a> {
     let mut graph = WeightedGraph::new();
     for line in input.lines() {
         let (src, dsts) = line.split_once(": ").unwrap();
@@ -142,22 +131,22 @@ fn parse<'a>(input: &'a str) -> WeightedGraph<'a> {
     graph
 }
 
-pub fn part1(input: &str) -> usize {
-    let graph = parse(input);
+pub fn part1(graph: &WeightedGraph) -> usize {
     let cluster = partition(graph.clone());
     let n = cluster.len();
     n * (graph.len() - n)
 }
 
-fn main() {
-    let input = include_str!("../../inputs/day25.txt");
-    println!("{}", part1(&input));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn print_graph(graph: &WeightedGraph) {
+        for (src, dsts) in graph.0.iter() {
+            println!("{:?} -> {:?}", src, dsts);
+        }
+    }
+
     #[test]
     fn test_paper() {
         let mut graph = WeightedGraph::new();
@@ -218,12 +207,6 @@ lsr: lhk
 rzs: qnr cmg lsr rsh
 frs: qnr lhk lsr
 ";
-        assert_eq!(part1(input), 54);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../../inputs/day25.txt");
-        //assert_eq!(part1(input), 0);
+        assert_eq!(part1(&parse(input)), 54);
     }
 }
@@ -0,0 +1,542 @@
+mod answers;
+mod day21;
+mod day25;
+mod output;
+
+use advent_of_code_2023::*;
+use answers::Status;
+use output::{Format, Record};
+use std::fmt::Debug;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(
+        "usage: advent_of_code_2023 <DAY> <FILE | -> [--part 1|2] [--variant NAME] [--param NAME=VALUE]... [--format text|json|csv] | all [--inputs DIR] [--format text|json|csv] | verify [--inputs DIR] [--answers FILE] | gen <DAY> [--seed S] [--size K]"
+    )]
+    Usage,
+    #[error("unknown day: {0}")]
+    UnknownDay(String),
+    #[error("{0} has no part {1}")]
+    UnknownPart(String, u8),
+    #[error("{0} has no variant {1}")]
+    UnknownVariant(String, String),
+    #[error("{0} has no parameter {1} (it has: {2})")]
+    UnknownParam(String, String, String),
+    #[error("bad value for {0}: {1}")]
+    BadParam(String, String),
+    #[error("{0} inputs can be at most size {1}")]
+    TooBig(String, usize),
+    #[error("answers file, line {0}: expected `[dayN]` or `partM = answer`")]
+    BadAnswers(usize),
+    #[error("{0}")]
+    IO(#[from] std::io::Error),
+}
+
+fn die(err: impl Into<Error>) -> ! {
+    std::eprintln!("{}", err.into());
+    std::process::exit(1);
+}
+
+trait Solve {
+    fn solve(&self, input: &str) -> String;
+}
+
+impl<T: Debug + 'static, F: Fn(&str) -> T> Solve for F {
+    fn solve(&self, input: &str) -> String {
+        format!("{:?}", self(input))
+    }
+}
+
+type Solver = Box<dyn Solve>;
+
+// the parts of a day, each solved from what its parse made of the input
+type Parts<'a> = [&'a dyn Fn() -> String];
+
+// parses an input and calls back with the time that took and the parts
+type Parse = dyn Fn(&str, &mut dyn FnMut(Duration, &Parts));
+
+// a day with how many parts it has
+struct Day {
+    parts: u8,
+    solve: Box<Parse>,
+}
+
+macro_rules! day {
+    ($parse:expr, $($part:expr),+) => {
+        Day {
+            parts: [$(stringify!($part)),+].len() as u8,
+            solve: Box::new(move |input: &str, solve: &mut dyn FnMut(Duration, &Parts)| {
+                let (parsed, elapsed) = timed(|| $parse(input));
+                solve(elapsed, &[$(&|| format!("{:?}", $part(&parsed))),+])
+            }),
+        }
+    };
+}
+
+// parts whose answer comes out of another program, which `all` can't time
+// and `verify` can't check
+const EXTERNAL: &[(&str, u8)] = &[("day24", 2)];
+
+// the parameters of the days that have any, see `params`
+#[derive(Clone, Copy, Default)]
+struct Params {
+    day2: day2::Params,
+    day11: day11::Params,
+    day12: day12::Params,
+    day14: day14::Params,
+    day20: day20::Params,
+    day21: day21::Params,
+    day24: day24::Params,
+}
+
+impl Params {
+    fn set(&mut self, day: &str, name: &str, value: &str) -> Result<(), Error> {
+        fn set<P: params::Params>(
+            params: &mut P,
+            day: &str,
+            name: &str,
+            value: &str,
+        ) -> Result<(), Error> {
+            params.set(name, value).map_err(|err| match err {
+                params::Error::Unknown => {
+                    Error::UnknownParam(day.to_string(), name.to_string(), P::NAMES.join(", "))
+                }
+                params::Error::Invalid => Error::BadParam(name.to_string(), value.to_string()),
+            })
+        }
+        match day {
+            "day2" => set(&mut self.day2, day, name, value),
+            "day11" => set(&mut self.day11, day, name, value),
+            "day12" => set(&mut self.day12, day, name, value),
+            "day14" => set(&mut self.day14, day, name, value),
+            "day20" => set(&mut self.day20, day, name, value),
+            "day21" => set(&mut self.day21, day, name, value),
+            "day24" => set(&mut self.day24, day, name, value),
+            _ => Err(Error::UnknownParam(day.to_string(), name.to_string(), "none".to_string())),
+        }
+    }
+}
+
+// each day's parse and parts, in order
+fn solutions(p: Params) -> Vec<(&'static str, Day)> {
+    vec![
+        ("day1", day!(day1::parse, day1::part1, day1::part2)),
+        ("day2", day!(day2::parse, |games| day2::part1_with(games, &p.day2), day2::part2)),
+        ("day3", day!(day3::parse, day3::part1, day3::part2)),
+        ("day4", day!(day4::parse, day4::part1, day4::part2)),
+        ("day5", day!(day5::parse, day5::part1, day5::part2)),
+        ("day6", day!(day6::parse, day6::part1, day6::part2)),
+        ("day7", day!(day7::parse, day7::part1, day7::part2)),
+        ("day8", day!(day8::parse, day8::part1, day8::part2)),
+        ("day9", day!(day9::parse, day9::part1, day9::part2)),
+        ("day10", day!(day10::parse, day10::part1, day10::part2)),
+        ("day11", day!(day11::parse, day11::part1, |image| day11::part2_with(image, &p.day11))),
+        ("day12", day!(day12::parse, day12::part1, |rows| day12::part2_with(rows, &p.day12))),
+        ("day13", day!(day13::parse, day13::part1, day13::part2)),
+        ("day14", day!(day14::parse, day14::part1, |grid| day14::part2_with(grid, &p.day14))),
+        ("day15", day!(day15::parse, day15::part1, day15::part2)),
+        ("day16", day!(day16::parse, day16::part1, day16::part2)),
+        ("day17", day!(day17::parse, day17::part1, day17::part2)),
+        ("day18", day!(day18::parse, day18::part1, day18::part2)),
+        ("day19", day!(day19::parse, day19::part1, day19::part2)),
+        ("day20", day!(day20::parse, |network| day20::part1_with(network, &p.day20), day20::part2)),
+        (
+            "day21",
+            day!(day21::parse, |garden| day21::part1_with(garden, &p.day21), |garden| {
+                day21::part2_with(garden, &p.day21)
+            }),
+        ),
+        ("day22", day!(day22::parse, day22::part1, day22::part2)),
+        ("day23", day!(day23::parse, day23::part1, day23::part2)),
+        ("day24", day!(day24::parse, |sys| day24::part1_with(sys, &p.day24), day24::part2)),
+        ("day25", day!(day25::parse, day25::part1)),
+    ]
+}
+
+// the variants of the days only the binary solves, like the library's in
+// `variants`, but given the parameters like `solutions`
+fn bin_variants(p: Params) -> Vec<(&'static str, u8, &'static str, Solver)> {
+    vec![
+        (
+            "day21",
+            2,
+            "extrapolation",
+            Box::new(move |input: &str| day21::part2_with(&day21::parse(input), &p.day21)),
+        ),
+        (
+            "day21",
+            2,
+            "simulation",
+            Box::new(move |input: &str| day21::part2_simulated(&day21::parse(input), &p.day21)),
+        ),
+    ]
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+// external parts print their program, so they're left out of anything that
+// nothing else may be mixed into, like the json and csv formats
+fn run(day: &'static str, part: u8, solve: &dyn Fn() -> String, external: bool) -> Record {
+    if !external && EXTERNAL.contains(&(day, part)) {
+        let (answer, elapsed) = (String::new(), Duration::ZERO);
+        return Record { day, part, answer, elapsed, status: output::Status::External };
+    }
+    let (answer, elapsed) = timed(solve);
+    Record { day, part, answer, elapsed, status: output::Status::Solved }
+}
+
+fn print(records: &[Record], format: Format) {
+    match format {
+        // text goes out as each part finishes, in step with anything it prints
+        Format::Text => {}
+        Format::Json => println!("{}", output::json(records)),
+        Format::Csv => print!("{}", output::csv(records)),
+    }
+}
+
+fn solve(day: &str, input: &str, opts: &Options) {
+    let (part, variant, format) = (opts.part, opts.variant.as_deref(), opts.format);
+    let mut params = Params::default();
+    for (name, value) in &opts.params {
+        params.set(day, name, value).unwrap_or_else(|err| die(err));
+    }
+    let solns = solutions(params);
+    let local = bin_variants(params);
+    let (day, soln) = solns
+        .iter()
+        .find(|soln| soln.0 == day)
+        .unwrap_or_else(|| die(Error::UnknownDay(day.to_string())));
+    let parts: Vec<u8> = (1..=soln.parts).filter(|n| part.is_none_or(|part| part == *n)).collect();
+    if let (Some(part), []) = (part, &parts[..]) {
+        die(Error::UnknownPart(day.to_string(), part));
+    }
+    // parts without the variant are solved the usual way
+    let num = day.trim_start_matches("day").parse().unwrap();
+    let find = |part: u8| -> Option<&dyn Solve> {
+        let name = variant?;
+        match variants::find(num, part, name) {
+            Some(variant) => Some(&variant.solve),
+            None => local.iter().find(|v| (v.0, v.1, v.2) == (*day, part, name)).map(|v| &*v.3),
+        }
+    };
+    if let Some(name) = variant.filter(|_| parts.iter().all(|part| find(*part).is_none())) {
+        die(Error::UnknownVariant(day.to_string(), name.to_string()));
+    }
+    // day 21's fit needs steps that end at an edge of the garden, which only
+    // its input can tell
+    if *day == "day21" && parts.contains(&2) && variant != Some("simulation") {
+        day21::check(&day21::parse(input), &params.day21).unwrap_or_else(|_| {
+            let steps = params.day21.part2_steps.to_string();
+            die(Error::BadParam("part2_steps".to_string(), steps))
+        });
+    }
+    let external = format == Format::Text;
+    let mut records = Vec::new();
+    (soln.solve)(input, &mut |_, solvers| {
+        for &part in &parts {
+            let record = match find(part) {
+                Some(variant) => run(day, part, &|| variant.solve(input), external),
+                None => run(day, part, solvers[part as usize - 1], external),
+            };
+            if format == Format::Text {
+                println!("{}", record.answer);
+            }
+            records.push(record);
+        }
+    });
+    print(&records, format);
+}
+
+// runs every day on its input in `dir`, timing its parse and then each part on
+// what it parsed
+fn solve_all(dir: &Path, format: Format) {
+    let mut rows = vec![["day", "part", "answer", "time"].map(String::from)];
+    let mut records = Vec::new();
+    let mut total = Duration::ZERO;
+    for (day, soln) in solutions(Params::default()) {
+        let path = dir.join(format!("{}.txt", day));
+        let input = std::fs::read_to_string(path).unwrap_or_else(|err| die(err));
+        (soln.solve)(&input, &mut |elapsed, solvers| {
+            total += elapsed;
+            rows.push([day.into(), "parse".into(), String::new(), format!("{:.1?}", elapsed)]);
+            for (part, solver) in (1..).zip(solvers) {
+                let record = run(day, part, *solver, false);
+                total += record.elapsed;
+                rows.push(match record.status {
+                    output::Status::Solved => {
+                        let time = format!("{:.1?}", record.elapsed);
+                        [day.into(), part.to_string(), record.answer.clone(), time]
+                    }
+                    output::Status::External => {
+                        [day.into(), part.to_string(), "(external)".into(), "-".into()]
+                    }
+                });
+                records.push(record);
+            }
+        });
+    }
+    if format != Format::Text {
+        return print(&records, format);
+    }
+    rows.push(["total".into(), String::new(), String::new(), format!("{:.1?}", total)]);
+    let width = |col: usize| rows.iter().map(|row| row[col].chars().count()).max().unwrap();
+    let widths = [width(0), width(1), width(2), width(3)];
+    for [day, part, answer, time] in &rows {
+        println!(
+            "{:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}",
+            day,
+            part,
+            answer,
+            time,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3]
+        );
+    }
+}
+
+// runs every day on its input in `dir` and checks the answers, returning
+// whether they all passed
+fn verify(dir: &Path, answers: &answers::Answers) -> bool {
+    let mut passed = true;
+    for (day, soln) in solutions(Params::default()) {
+        let input = std::fs::read_to_string(dir.join(format!("{}.txt", day)));
+        let input = input.unwrap_or_else(|err| die(err));
+        (soln.solve)(&input, &mut |_, solvers| {
+            for (part, solver) in (1..).zip(solvers) {
+                let (got, status) = if EXTERNAL.contains(&(day, part)) {
+                    (String::new(), Status::Skip)
+                } else {
+                    let got = solver();
+                    let status = answers::check(answers, day, part, &got);
+                    (got, status)
+                };
+                match status {
+                    Status::Pass => println!("{:<6} part{}  PASS     {}", day, part, got),
+                    Status::Fail { expected } => {
+                        passed = false;
+                        println!(
+                            "{:<6} part{}  FAIL     expected {}, got {}",
+                            day, part, expected, got
+                        )
+                    }
+                    Status::Missing => println!("{:<6} part{}  MISSING  got {}", day, part, got),
+                    Status::Skip => println!("{:<6} part{}  SKIP", day, part),
+                }
+            }
+        });
+    }
+    passed
+}
+
+enum Mode {
+    // an input of `-` is read from stdin
+    One { day: String, input: PathBuf },
+    All,
+    Verify,
+    Gen { day: String },
+}
+
+struct Options {
+    mode: Mode,
+    part: Option<u8>,
+    variant: Option<String>,
+    // name and value, for the day in `Mode::One`
+    params: Vec<(String, String)>,
+    format: Format,
+    inputs: PathBuf,
+    answers: PathBuf,
+    // for `Mode::Gen`, which defaults to the size of the real input
+    seed: u64,
+    size: Option<usize>,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, Error> {
+    let mode = match args.next().ok_or(Error::Usage)?.as_str() {
+        "all" => Mode::All,
+        "verify" => Mode::Verify,
+        "gen" => Mode::Gen { day: args.next().ok_or(Error::Usage)? },
+        day => Mode::One { day: day.to_string(), input: args.next().ok_or(Error::Usage)?.into() },
+    };
+    let mut opts = Options {
+        mode,
+        part: None,
+        variant: None,
+        params: Vec::new(),
+        format: Format::Text,
+        inputs: PathBuf::from("inputs"),
+        answers: PathBuf::from("answers.toml"),
+        seed: 0,
+        size: None,
+    };
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(Error::Usage)?;
+        match (&opts.mode, arg.as_str()) {
+            (Mode::One { .. }, "--part") => {
+                opts.part = Some(value.parse().map_err(|_| Error::Usage)?)
+            }
+            (Mode::One { .. }, "--variant") => opts.variant = Some(value),
+            (Mode::One { .. }, "--param") => {
+                let (name, value) = value.split_once('=').ok_or(Error::Usage)?;
+                opts.params.push((name.to_string(), value.to_string()))
+            }
+            (Mode::One { .. } | Mode::All, "--format") => {
+                opts.format = value.parse().map_err(|_| Error::Usage)?
+            }
+            (Mode::All | Mode::Verify, "--inputs") => opts.inputs = PathBuf::from(value),
+            (Mode::Verify, "--answers") => opts.answers = PathBuf::from(value),
+            (Mode::Gen { .. }, "--seed") => opts.seed = value.parse().map_err(|_| Error::Usage)?,
+            (Mode::Gen { .. }, "--size") => {
+                opts.size = Some(value.parse().map_err(|_| Error::Usage)?)
+            }
+            _ => return Err(Error::Usage),
+        }
+    }
+    Ok(opts)
+}
+
+// prints a random input for `day`, see `gen`
+fn generate(day: &str, seed: u64, size: Option<usize>) -> Result<String, Error> {
+    let generator = day
+        .strip_prefix("day")
+        .and_then(|num| num.parse().ok())
+        .and_then(gen::find)
+        .ok_or_else(|| Error::UnknownDay(day.to_string()))?;
+    let size = size.unwrap_or(generator.size);
+    if size > generator.max_size {
+        return Err(Error::TooBig(day.to_string(), generator.max_size));
+    }
+    let mut input = String::new();
+    (generator.generate)(&mut gen::Rng::new(seed), size, &mut input).unwrap();
+    Ok(input)
+}
+
+fn main() {
+    let opts = parse_options(std::env::args().skip(1)).unwrap_or_else(|err| die(err));
+    match &opts.mode {
+        Mode::One { day, input } => {
+            let input = if input.as_os_str() == "-" {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input).map(|_| input)
+            } else {
+                std::fs::read_to_string(input)
+            };
+            let input = input.unwrap_or_else(|err| die(err));
+            solve(day, &input, &opts);
+        }
+        Mode::All => solve_all(&opts.inputs, opts.format),
+        Mode::Verify => {
+            let text = std::fs::read_to_string(&opts.answers).unwrap_or_else(|err| die(err));
+            let answers = answers::parse(&text).unwrap_or_else(|line| die(Error::BadAnswers(line)));
+            if !verify(&opts.inputs, &answers) {
+                std::process::exit(1);
+            }
+        }
+        Mode::Gen { day } => {
+            print!("{}", generate(day, opts.seed, opts.size).unwrap_or_else(|err| die(err)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // parts that take over a minute unoptimized
+    const SLOW: &[(&str, u8)] = &[("day25", 1)];
+
+    // checks the parts that `wanted` picks out against answers.toml
+    fn check_answers(wanted: impl Fn(&str, u8) -> bool) {
+        let answers = answers::parse(include_str!("../../../answers.toml")).unwrap();
+        for (day, soln) in solutions(Params::default()) {
+            if !(1..=soln.parts).any(|part| wanted(day, part)) {
+                continue;
+            }
+            let input = std::fs::read_to_string(format!("inputs/{}.txt", day)).unwrap();
+            (soln.solve)(&input, &mut |_, solvers| {
+                for (part, solver) in (1..).zip(solvers).filter(|(part, _)| wanted(day, *part)) {
+                    let got = solver();
+                    let status = answers::check(&answers, day, part, &got);
+                    assert_eq!(status, Status::Pass, "{} part{}: got {}", day, part, got);
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        check_answers(|day, part| ![EXTERNAL, SLOW].concat().contains(&(day, part)));
+    }
+
+    // run with `cargo test -- --ignored`, or in release with `verify`
+    #[test]
+    #[ignore]
+    fn test_slow_answers() {
+        check_answers(|day, part| SLOW.contains(&(day, part)));
+    }
+
+    #[test]
+    fn test_run() {
+        // only the parts that come out of another program are left out
+        let record = run("day25", 1, &|| "54".to_string(), false);
+        assert_eq!((record.answer.as_str(), record.status), ("54", output::Status::Solved));
+        let record = run("day24", 2, &|| unreachable!(), false);
+        assert_eq!(record.status, output::Status::External);
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        // the real input, and generated ones, out to where the fit holds
+        let mut inputs = vec![(std::fs::read_to_string("inputs/day21.txt").unwrap(), 65, 131)];
+        inputs.extend((0..8).map(|seed| (generate("day21", seed, Some(11)).unwrap(), 5, 11)));
+        for (input, half, size) in inputs {
+            for k in 1..=3 {
+                let mut params = Params::default();
+                params.day21.part2_steps = half + k * size;
+                let answers: Vec<_> = bin_variants(params)
+                    .iter()
+                    .filter(|v| v.0 == "day21")
+                    .map(|v| (v.2, v.3.solve(&input)))
+                    .collect();
+                for (name, got) in &answers[1..] {
+                    assert_eq!(*got, answers[0].1, "day21 part2: {} vs {}", name, answers[0].0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        assert!(matches!(generate("day6", 0, Some(5)), Err(Error::TooBig(..))));
+        assert!(matches!(generate("day26", 0, None), Err(Error::UnknownDay(..))));
+        assert_eq!(generate("day7", 1, Some(3)).unwrap(), generate("day7", 1, Some(3)).unwrap());
+        // the days the library doesn't solve
+        for seed in 0..8 {
+            let input = generate("day21", seed, Some(11)).unwrap();
+            let garden = day21::parse(&input);
+            let params = day21::Params { part1_steps: 6, part2_steps: 5 + 11 * 4 };
+            assert!(day21::part1_with(&garden, &params) > 0);
+            assert!(day21::part2_with(&garden, &params) > 0);
+            // two groups of at least five
+            let product = day25::part1(&day25::parse(&generate("day25", seed, Some(20)).unwrap()));
+            assert!((5..=15).any(|n| n * (20 - n) == product), "{}", product);
+        }
+        // day 21 at its largest too. day 25 at its largest is like the real
+        // input, which test_slow_answers covers
+        for seed in 0..3 {
+            let garden = day21::parse(&generate("day21", seed, None).unwrap());
+            let params = day21::Params::default();
+            assert!(day21::part1_with(&garden, &params) > 0);
+            assert!(day21::part2_with(&garden, &params) > 0);
+        }
+    }
+}
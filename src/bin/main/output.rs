@@ -0,0 +1,134 @@
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Solved,
+    // the answer comes out of another program, so there's none here
+    External,
+}
+
+impl Status {
+    fn name(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::External => "external",
+        }
+    }
+}
+
+// one part's run, as the json and csv formats report it
+#[derive(Debug)]
+pub struct Record {
+    pub day: &'static str,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out + "\""
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// one object per line inside the array, so it still greps
+pub fn json(records: &[Record]) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"elapsed_ns\": {}, \"status\": {}}}",
+                json_string(r.day),
+                r.part,
+                json_string(&r.answer),
+                r.elapsed.as_nanos(),
+                json_string(r.status.name())
+            )
+        })
+        .collect();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+pub fn csv(records: &[Record]) -> String {
+    let mut out = String::from("day,part,answer,elapsed_ns,status\n");
+    for r in records {
+        let answer = csv_field(&r.answer);
+        let (ns, status) = (r.elapsed.as_nanos(), r.status.name());
+        writeln!(out, "{},{},{},{},{}", r.day, r.part, answer, ns, status).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        let records = [
+            Record {
+                day: "day1",
+                part: 1,
+                answer: "142".into(),
+                elapsed: Duration::from_micros(3),
+                status: Status::Solved,
+            },
+            Record {
+                day: "day24",
+                part: 2,
+                answer: "a \"b\", c".into(),
+                elapsed: Duration::ZERO,
+                status: Status::External,
+            },
+        ];
+        assert_eq!(
+            json(&records),
+            r#"[
+  {"day": "day1", "part": 1, "answer": "142", "elapsed_ns": 3000, "status": "solved"},
+  {"day": "day24", "part": 2, "answer": "a \"b\", c", "elapsed_ns": 0, "status": "external"}
+]"#
+        );
+        assert_eq!(
+            csv(&records),
+            "day,part,answer,elapsed_ns,status\nday1,1,142,3000,solved\nday24,2,\"a \"\"b\"\", c\",0,external\n"
+        );
+    }
+}
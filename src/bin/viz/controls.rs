@@ -0,0 +1,140 @@
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Restart,
+    Quit,
+}
+
+impl Key {
+    pub fn from_char(c: char) -> Option<Key> {
+        match c {
+            ' ' => Some(Key::Pause),
+            '.' => Some(Key::Step),
+            '+' | '=' => Some(Key::Faster),
+            '-' | '_' => Some(Key::Slower),
+            'r' => Some(Key::Restart),
+            // ctrl-c arrives as a plain byte while the terminal is raw
+            'q' | '\x03' => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+// why the solver was stopped before it finished
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stop {
+    Restart,
+    Quit,
+}
+
+struct State {
+    paused: bool,
+    steps: usize,
+    delay: Duration,
+    restart: bool,
+    quit: bool,
+}
+
+// keys pressed in the renderer, as seen by the solver thread at each frame
+pub struct Remote {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+impl Remote {
+    pub fn new(delay: Duration, paused: bool) -> Self {
+        let state = State { paused, steps: 0, delay, restart: false, quit: false };
+        Remote { state: Mutex::new(state), wake: Condvar::new() }
+    }
+
+    pub fn press(&self, key: Key) {
+        let mut state = self.state.lock().unwrap();
+        match key {
+            Key::Pause => state.paused = !state.paused,
+            Key::Step => {
+                state.paused = true;
+                state.steps += 1;
+            }
+            Key::Faster => state.delay /= 2,
+            Key::Slower => {
+                state.delay = (state.delay * 2).clamp(Duration::from_micros(1), MAX_DELAY)
+            }
+            Key::Restart => {
+                state.steps = 0;
+                state.restart = true;
+            }
+            Key::Quit => state.quit = true,
+        }
+        self.wake.notify_all();
+    }
+
+    // blocks while paused, then says how long to show the frame for
+    pub fn wait_frame(&self) -> Result<Duration, Stop> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.quit {
+                return Err(Stop::Quit);
+            }
+            if state.restart {
+                state.restart = false;
+                return Err(Stop::Restart);
+            }
+            if !state.paused {
+                return Ok(state.delay);
+            }
+            if state.steps > 0 {
+                state.steps -= 1;
+                return Ok(Duration::ZERO);
+            }
+            state = self.wake.wait(state).unwrap();
+        }
+    }
+
+    // blocks once the solver is done until the run is restarted or abandoned
+    pub fn wait_done(&self) -> Stop {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.quit {
+                return Stop::Quit;
+            }
+            if state.restart {
+                state.restart = false;
+                return Stop::Restart;
+            }
+            state = self.wake.wait(state).unwrap();
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let state = self.state.lock().unwrap();
+        let paused = if state.paused { " | paused" } else { "" };
+        format!("{:?}/frame{}", state.delay, paused)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step_and_restart() {
+        let remote = Remote::new(Duration::from_micros(100), true);
+        remote.press(Key::Step);
+        assert_eq!(remote.wait_frame(), Ok(Duration::ZERO));
+        remote.press(Key::Pause);
+        remote.press(Key::Faster);
+        assert_eq!(remote.wait_frame(), Ok(Duration::from_micros(50)));
+        remote.press(Key::Restart);
+        assert_eq!(remote.wait_frame(), Err(Stop::Restart));
+        remote.press(Key::Quit);
+        assert_eq!(remote.wait_done(), Stop::Quit);
+    }
+}
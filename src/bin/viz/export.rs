@@ -0,0 +1,251 @@
+use super::{Buffer, Canvas, Color, Pos, Sprite};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Format::Ppm),
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            _ => Err(()),
+        }
+    }
+}
+
+// indexed by `Color as u8`, which is also the gif palette index
+const COLORS: [Color; 7] =
+    [Color::Black, Color::Gray, Color::White, Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+impl Color {
+    fn rgb8(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0, 0, 0],
+            Color::Gray => [102, 102, 102],
+            Color::White => [255, 255, 255],
+            Color::Red => [255, 0, 0],
+            Color::Green => [0, 255, 0],
+            Color::Blue => [0, 0, 255],
+            Color::Yellow => [255, 255, 0],
+        }
+    }
+}
+
+// a palette index per pixel. each cell is a 3x3 grid of subtiles, as in the
+// windowed renderer, and each subtile is `scale` pixels wide.
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn rasterize(buffer: &Buffer, scale: usize) -> Image {
+        let (rows, cols) = (buffer.len(), buffer[0].len());
+        let (width, height) = (cols * 3 * scale, rows * 3 * scale);
+        let mut pixels = vec![Color::Black as u8; width * height];
+        for (row, sprites) in buffer.iter().enumerate() {
+            for (col, sprite) in sprites.iter().enumerate() {
+                for &(i, j) in sprite.shape() {
+                    let x = (col * 3 + i as usize) * scale;
+                    let y = (row * 3 + j as usize) * scale;
+                    for y in y..y + scale {
+                        pixels[y * width + x..y * width + x + scale].fill(sprite.color as u8);
+                    }
+                }
+            }
+        }
+        Image { width, height, pixels }
+    }
+
+    fn rgb(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|pixel| COLORS[*pixel as usize].rgb8()).collect()
+    }
+
+    pub fn write_ppm(&self, w: &mut impl Write) -> io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.rgb())
+    }
+
+    pub fn write_png(&self, w: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb())?;
+        Ok(writer.finish()?)
+    }
+}
+
+enum Sink {
+    Files { dir: PathBuf, format: Format },
+    Gif(gif::Encoder<BufWriter<File>>),
+}
+
+// draws offline into its own buffer and writes out every `every`th frame,
+// plus the final state once the solver is done
+pub struct Recorder {
+    buffer: Buffer,
+    sink: Sink,
+    every: usize,
+    scale: usize,
+    delay: u16,
+    frames: usize,
+    written: usize,
+    dirty: bool,
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    pub fn new(
+        buffer: Buffer,
+        out: PathBuf,
+        format: Format,
+        every: usize,
+        scale: usize,
+        delay: Duration,
+    ) -> io::Result<Self> {
+        let sink = match format {
+            Format::Gif => {
+                let (width, height) = (buffer[0].len() * 3 * scale, buffer.len() * 3 * scale);
+                let palette: Vec<u8> = COLORS.iter().flat_map(|color| color.rgb8()).collect();
+                let file = BufWriter::new(File::create(out)?);
+                let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &palette)
+                    .map_err(io::Error::other)?;
+                encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+                Sink::Gif(encoder)
+            }
+            format => {
+                fs::create_dir_all(&out)?;
+                Sink::Files { dir: out, format }
+            }
+        };
+        // gif delays are in hundredths of a second, and most viewers
+        // don't go faster than 2
+        let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+        Ok(Self {
+            buffer,
+            sink,
+            every,
+            scale,
+            delay,
+            frames: 0,
+            written: 0,
+            dirty: true,
+            error: None,
+        })
+    }
+
+    fn write(&mut self) -> io::Result<()> {
+        let image = Image::rasterize(&self.buffer, self.scale);
+        match &mut self.sink {
+            Sink::Files { dir, format } => {
+                let ext = if *format == Format::Ppm { "ppm" } else { "png" };
+                let path = dir.join(format!("frame{:06}.{}", self.written, ext));
+                let mut file = BufWriter::new(File::create(path)?);
+                match format {
+                    Format::Ppm => image.write_ppm(&mut file)?,
+                    _ => image.write_png(&mut file)?,
+                }
+                file.flush()?;
+            }
+            Sink::Gif(encoder) => {
+                let frame = gif::Frame {
+                    width: image.width as u16,
+                    height: image.height as u16,
+                    delay: self.delay,
+                    buffer: image.pixels.into(),
+                    ..Default::default()
+                };
+                encoder.write_frame(&frame).map_err(io::Error::other)?;
+            }
+        }
+        self.written += 1;
+        self.dirty = false;
+        Ok(())
+    }
+
+    // writes the final frame and returns how many frames were written
+    pub fn finish(mut self) -> io::Result<usize> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        if self.dirty {
+            self.write()?;
+        }
+        if let Sink::Gif(encoder) = self.sink {
+            encoder.into_inner()?.flush()?;
+        }
+        Ok(self.written)
+    }
+}
+
+impl Canvas for Recorder {
+    fn draw(&mut self, (row, col): Pos, sprite: Sprite) {
+        self.buffer[row][col] = sprite;
+        self.dirty = true;
+    }
+
+    fn frame(&mut self) {
+        self.frames += 1;
+        if self.error.is_none() && self.frames.is_multiple_of(self.every) {
+            self.error = self.write().err();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{empty_buffer, Painter};
+    use super::*;
+    use advent_of_code_2023::day10;
+
+    #[test]
+    fn test_final_frame() {
+        let input = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+        // one per test run, so that concurrent runs don't share frames
+        let name = format!("viz-export-test-final-frame-{}", std::process::id());
+        let dir = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        let mut recorder = Recorder::new(
+            empty_buffer(input),
+            dir.clone(),
+            Format::Ppm,
+            usize::MAX,
+            1,
+            Duration::ZERO,
+        )
+        .unwrap();
+        day10::part2_observed(&day10::parse(input), &mut Painter(&mut recorder));
+        assert_eq!(recorder.finish().unwrap(), 1);
+
+        let ppm = fs::read(dir.join("frame000000.ppm")).unwrap();
+        let header = b"P6\n30 27\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = ppm[header.len()..].chunks(3);
+        let red = pixels.filter(|pixel| *pixel == Color::Red.rgb8()).count();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(red, 4 * 9);
+    }
+}
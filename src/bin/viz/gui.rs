@@ -0,0 +1,176 @@
+use super::controls::Key;
+use super::font;
+use super::screen::{Event, Screen};
+use super::{Color, Live, Pos, Sprite};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use tetra::graphics::{self, Color as Rgb};
+use tetra::graphics::{
+    mesh::{Mesh, ShapeStyle},
+    DrawParams, Rectangle,
+};
+use tetra::input::{self, Key as Pressed};
+use tetra::math;
+use tetra::window;
+use tetra::{Context, ContextBuilder, State};
+
+const WIDTH: i32 = 1024;
+const HEIGHT: i32 = 768;
+const SUBTILES: f32 = 3.;
+// the counters' pixels, and the margin around them
+const PIXEL: f32 = 3.;
+const MARGIN: f32 = 8.;
+
+const KEYS: &[(Pressed, Key)] = &[
+    (Pressed::Space, Key::Pause),
+    (Pressed::Period, Key::Step),
+    (Pressed::Equals, Key::Faster),
+    (Pressed::NumPadPlus, Key::Faster),
+    (Pressed::Minus, Key::Slower),
+    (Pressed::NumPadMinus, Key::Slower),
+    (Pressed::R, Key::Restart),
+    (Pressed::Q, Key::Quit),
+    (Pressed::Escape, Key::Quit),
+];
+
+impl Color {
+    fn rgb(self) -> Rgb {
+        match self {
+            Color::Black => Rgb::BLACK,
+            Color::Gray => Rgb::rgb(0.4, 0.4, 0.4),
+            Color::White => Rgb::WHITE,
+            Color::Red => Rgb::RED,
+            Color::Green => Rgb::GREEN,
+            Color::Blue => Rgb::BLUE,
+            Color::Yellow => Rgb::rgb(1., 1., 0.),
+        }
+    }
+}
+
+impl Sprite {
+    fn draw(self, ctx: &mut Context, tile: &Mesh, pos: math::Vec2<f32>, rect: Rectangle) {
+        for &(i, j) in self.shape() {
+            let pos =
+                pos.with_x(pos.x + i as f32 * rect.width).with_y(pos.y + j as f32 * rect.height);
+            tile.draw(ctx, DrawParams::new().position(pos).color(self.color.rgb()));
+        }
+    }
+}
+
+struct VizState {
+    live: Arc<Live>,
+    screen: Screen,
+    events: Receiver<Event>,
+    // keeps what was drawn between frames, so that only dirty cells are redrawn
+    canvas: graphics::Canvas,
+    origin: math::Vec2<f32>,
+    rect: Rectangle,
+    tile: Mesh,
+    cell: Mesh,
+    pixel: Mesh,
+}
+
+impl VizState {
+    // square tiles as large as fit the window, with the grid centered
+    fn new(
+        ctx: &mut Context,
+        live: Arc<Live>,
+        screen: Screen,
+        events: Receiver<Event>,
+    ) -> tetra::Result<Self> {
+        let (rows, cols) = (screen.cells.len() as f32, screen.cells[0].len() as f32);
+        let size = (WIDTH as f32 / cols).min(HEIGHT as f32 / rows) / SUBTILES;
+        let origin = math::Vec2::new(
+            (WIDTH as f32 - cols * size * SUBTILES) / 2.,
+            (HEIGHT as f32 - rows * size * SUBTILES) / 2.,
+        );
+        let rect = Rectangle::new(0., 0., size, size);
+        let tile = Mesh::rectangle(ctx, ShapeStyle::Fill, rect)?;
+        let cell = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(0., 0., size * SUBTILES, size * SUBTILES),
+        )?;
+        let pixel = Mesh::rectangle(ctx, ShapeStyle::Fill, Rectangle::new(0., 0., PIXEL, PIXEL))?;
+        let canvas = graphics::Canvas::new(ctx, WIDTH, HEIGHT)?;
+        Ok(VizState { live, screen, events, canvas, origin, tile, cell, rect, pixel })
+    }
+
+    fn position(&self, (row, col): Pos) -> math::Vec2<f32> {
+        self.origin
+            + math::Vec2::new(
+                col as f32 * self.rect.width * SUBTILES,
+                row as f32 * self.rect.height * SUBTILES,
+            )
+    }
+
+    fn draw_cell(&self, ctx: &mut Context, pos: Pos) {
+        let at = self.position(pos);
+        self.cell.draw(ctx, DrawParams::new().position(at).color(Rgb::BLACK));
+        self.screen.cells[pos.0][pos.1].draw(ctx, &self.tile, at, self.rect);
+    }
+
+    // the counters in the top left, over a black box so the grid doesn't show
+    // through them. drawn every frame, after the canvas
+    fn draw_counters(&self, ctx: &mut Context) {
+        let counters = self.screen.counters(&self.live);
+        let longest = counters.iter().map(|counter| counter.len()).max().unwrap_or(0);
+        let (width, height) = (longest * (font::WIDTH + 1), counters.len() * (font::HEIGHT + 1));
+        // the pixel mesh, stretched over the text and a pixel around it
+        let backing = DrawParams::new()
+            .position(math::Vec2::new(MARGIN - PIXEL, MARGIN - PIXEL))
+            .scale(math::Vec2::new(width as f32 + 2., height as f32 + 1.))
+            .color(Rgb::BLACK);
+        self.pixel.draw(ctx, backing);
+        for (line, counter) in counters.iter().enumerate() {
+            for (x, y) in font::pixels(counter) {
+                let y = line * (font::HEIGHT + 1) + y;
+                let at = math::Vec2::new(MARGIN + x as f32 * PIXEL, MARGIN + y as f32 * PIXEL);
+                self.pixel.draw(ctx, DrawParams::new().position(at).color(Rgb::WHITE));
+            }
+        }
+    }
+}
+
+impl State for VizState {
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        for &(pressed, key) in KEYS {
+            if input::is_key_pressed(ctx, pressed) {
+                self.live.remote.press(key);
+                if key == Key::Quit {
+                    window::quit(ctx);
+                }
+            }
+        }
+        window::set_title(ctx, self.screen.status(&self.live));
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        self.screen.drain(&self.events);
+        graphics::set_canvas(ctx, &self.canvas);
+        match self.screen.take_dirty() {
+            None => {
+                graphics::clear(ctx, Rgb::BLACK);
+                for (row, sprites) in self.screen.cells.iter().enumerate() {
+                    for col in 0..sprites.len() {
+                        self.draw_cell(ctx, (row, col));
+                    }
+                }
+            }
+            Some(dirty) => dirty.into_iter().for_each(|pos| self.draw_cell(ctx, pos)),
+        }
+        graphics::reset_canvas(ctx);
+        graphics::clear(ctx, Rgb::BLACK);
+        self.canvas.draw(ctx, math::Vec2::zero());
+        self.draw_counters(ctx);
+        Ok(())
+    }
+}
+
+pub fn run(live: Arc<Live>, screen: Screen, events: Receiver<Event>) -> tetra::Result {
+    let title = screen.status(&live);
+    ContextBuilder::new(title, WIDTH, HEIGHT)
+        .build()?
+        .run(|ctx| VizState::new(ctx, live, screen, events))
+}
@@ -0,0 +1,388 @@
+mod controls;
+mod export;
+#[cfg(any(feature = "gui", test))]
+mod font;
+#[cfg(feature = "gui")]
+mod gui;
+mod screen;
+mod term;
+
+use advent_of_code_2023::viz::{Observer, Pos};
+use advent_of_code_2023::{day10, day14, day16, day17, day23};
+use controls::{Remote, Stop};
+use export::{Format, Recorder};
+use screen::{Event, Screen};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(
+        "usage: viz <DAY> [FILE] [--term] [--paused] [--out <DIR | FILE.gif>] [--format ppm|png] [--every N] [--scale N]"
+    )]
+    Usage,
+    #[error("no visualization for day: {0}")]
+    UnknownDay(String),
+    #[error("{0}")]
+    IO(#[from] std::io::Error),
+}
+
+fn die(err: impl std::fmt::Display) -> ! {
+    eprintln!("{}", err);
+    std::process::exit(1);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Color {
+    Black,
+    Gray,
+    White,
+    Red,
+    Green,
+    Blue,
+    Yellow,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Sprite {
+    glyph: char,
+    color: Color,
+}
+
+const EMPTY: Sprite = Sprite { glyph: ' ', color: Color::Black };
+
+fn sprite(glyph: char, color: Color) -> Sprite {
+    Sprite { glyph, color }
+}
+
+impl Sprite {
+    // which of the 3x3 subtiles are filled in, as (x, y) offsets
+    fn shape(self) -> &'static [(u8, u8)] {
+        match self.glyph {
+            '─' => &[(0, 1), (1, 1), (2, 1)],
+            '│' => &[(1, 0), (1, 1), (1, 2)],
+            '┐' => &[(0, 1), (1, 1), (1, 2)],
+            '└' => &[(1, 0), (1, 1), (2, 1)],
+            '┌' => &[(2, 1), (1, 1), (1, 2)],
+            '┘' => &[(0, 1), (1, 1), (1, 0)],
+            '╱' => &[(2, 0), (1, 1), (0, 2)],
+            '╲' => &[(0, 0), (1, 1), (2, 2)],
+            '●' => &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            _ => &[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)],
+        }
+    }
+}
+
+// how each day's cells are drawn
+trait Paint {
+    fn paint(self) -> Sprite;
+}
+
+fn pipe(tile: u8) -> char {
+    match tile {
+        b'-' => '─',
+        b'|' => '│',
+        b'7' => '┐',
+        b'L' => '└',
+        b'F' => '┌',
+        b'J' => '┘',
+        b'S' => '█',
+        _ => panic!("invalid loop"),
+    }
+}
+
+impl Paint for day10::Cell {
+    fn paint(self) -> Sprite {
+        match self {
+            day10::Cell::Loop(tile) => sprite(pipe(tile), Color::Green),
+            day10::Cell::Walk(tile) => sprite(pipe(tile), Color::Blue),
+            day10::Cell::Interior => sprite('█', Color::Red),
+        }
+    }
+}
+
+impl Paint for day14::Tile {
+    fn paint(self) -> Sprite {
+        match self {
+            day14::Tile::Empty => EMPTY,
+            day14::Tile::Round => sprite('●', Color::White),
+            day14::Tile::Cube => sprite('█', Color::Gray),
+        }
+    }
+}
+
+fn optic(tile: day16::Tile) -> char {
+    match tile {
+        day16::Tile::Empty => '█',
+        day16::Tile::MirrorUp => '╱',
+        day16::Tile::MirrorDown => '╲',
+        day16::Tile::SplitUpDown => '│',
+        day16::Tile::SplitLeftRight => '─',
+    }
+}
+
+impl Paint for day16::Cell {
+    fn paint(self) -> Sprite {
+        match self {
+            day16::Cell::Tile(day16::Tile::Empty) => EMPTY,
+            day16::Cell::Tile(tile) => sprite(optic(tile), Color::Gray),
+            day16::Cell::Energized(tile) => sprite(optic(tile), Color::Yellow),
+        }
+    }
+}
+
+impl Paint for day17::Cell {
+    fn paint(self) -> Sprite {
+        match self {
+            day17::Cell::Block(loss) => sprite((b'0' + loss) as char, Color::Gray),
+            day17::Cell::Frontier => sprite('█', Color::Blue),
+            day17::Cell::Settled => sprite('█', Color::Red),
+        }
+    }
+}
+
+impl Paint for day23::Cell {
+    fn paint(self) -> Sprite {
+        match self {
+            day23::Cell::Wall => sprite('█', Color::Gray),
+            day23::Cell::Corridor => sprite('█', Color::Blue),
+            day23::Cell::Junction => sprite('█', Color::Green),
+            day23::Cell::Path => sprite('█', Color::Red),
+        }
+    }
+}
+
+type Buffer = Vec<Vec<Sprite>>;
+
+fn empty_buffer(input: &str) -> Buffer {
+    let cols = input.lines().next().unwrap().len();
+    let rows = input.lines().count();
+    vec![vec![EMPTY; cols]; rows]
+}
+
+// something that the painted cells are drawn onto
+trait Canvas {
+    fn draw(&mut self, pos: Pos, sprite: Sprite);
+    fn frame(&mut self);
+
+    fn stopped(&self) -> bool {
+        false
+    }
+}
+
+// adapts a canvas to observe any day whose cells can be painted
+struct Painter<'a>(&'a mut dyn Canvas);
+
+impl<C: Paint> Observer<C> for Painter<'_> {
+    fn cell_changed(&mut self, pos: Pos, cell: C) {
+        self.0.draw(pos, cell.paint());
+    }
+
+    fn frame(&mut self) {
+        self.0.frame();
+    }
+
+    fn stopped(&self) -> bool {
+        self.0.stopped()
+    }
+}
+
+// state shared between the solver thread and the renderer
+struct Live {
+    viz: &'static Viz,
+    remote: Remote,
+}
+
+// sends what is drawn to the renderer, waiting on the controls at every frame
+// until they stop the run
+struct Visualizer<'a> {
+    live: &'a Live,
+    events: &'a SyncSender<Event>,
+    stop: Option<Stop>,
+}
+
+impl Visualizer<'_> {
+    // the renderer only goes away once the process is exiting, so there is
+    // nobody left to tell when a send fails
+    fn send(&self, event: Event) {
+        self.events.send(event).ok();
+    }
+}
+
+impl Canvas for Visualizer<'_> {
+    fn draw(&mut self, pos: Pos, sprite: Sprite) {
+        if !self.stopped() {
+            self.send(Event::Draw(pos, sprite));
+        }
+    }
+
+    // the solvers check `stopped` after each frame, but may still pass
+    // through a few more on their way out
+    fn frame(&mut self) {
+        if self.stopped() {
+            return;
+        }
+        self.send(Event::Frame);
+        match self.live.remote.wait_frame() {
+            Ok(delay) => thread::sleep(delay),
+            Err(stop) => self.stop = Some(stop),
+        }
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.is_some()
+    }
+}
+
+// runs the solver until quit, starting over whenever it is restarted
+fn solve(live: &Live, input: &str, events: SyncSender<Event>, interactive: bool) {
+    loop {
+        let mut visualizer = Visualizer { live, events: &events, stop: None };
+        visualizer.send(Event::Reset);
+        (live.viz.run)(input, &mut Painter(&mut visualizer));
+        let stop = match visualizer.stop {
+            Some(stop) => stop,
+            None if !interactive => return,
+            None => live.remote.wait_done(),
+        };
+        if stop == Stop::Quit {
+            return;
+        }
+    }
+}
+
+struct Viz {
+    day: &'static str,
+    delay: Duration,
+    counters: &'static [(&'static str, &'static [Color])],
+    run: fn(&str, &mut Painter),
+}
+
+const VIZS: &[Viz] = &[
+    Viz {
+        day: "day10",
+        delay: Duration::from_micros(100),
+        counters: &[("loop", &[Color::Green, Color::Blue]), ("interior", &[Color::Red])],
+        run: |input, viz| {
+            day10::part2_observed(&day10::parse(input), viz);
+        },
+    },
+    Viz {
+        day: "day14",
+        delay: Duration::from_millis(20),
+        counters: &[("round", &[Color::White]), ("cube", &[Color::Gray])],
+        run: |input, viz| {
+            day14::part2_observed(&day14::parse(input), viz);
+        },
+    },
+    Viz {
+        day: "day16",
+        delay: Duration::from_micros(500),
+        counters: &[("energized", &[Color::Yellow])],
+        run: |input, viz| {
+            day16::part1_observed(&day16::parse(input), viz);
+        },
+    },
+    Viz {
+        day: "day17",
+        delay: Duration::from_micros(50),
+        counters: &[("frontier", &[Color::Blue]), ("settled", &[Color::Red])],
+        run: |input, viz| {
+            day17::part1_observed(&day17::parse(input), viz);
+        },
+    },
+    Viz {
+        day: "day23",
+        delay: Duration::from_micros(200),
+        counters: &[("junctions", &[Color::Green]), ("path", &[Color::Red])],
+        run: |input, viz| {
+            day23::part1_observed(&day23::parse(input), viz);
+        },
+    },
+];
+
+struct Options {
+    day: String,
+    input: PathBuf,
+    windowed: bool,
+    paused: bool,
+    out: Option<PathBuf>,
+    format: Format,
+    every: usize,
+    scale: usize,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, Error> {
+    let day = args.next().ok_or(Error::Usage)?;
+    let mut opts = Options {
+        input: PathBuf::from(format!("inputs/{}.txt", day)),
+        day,
+        windowed: cfg!(feature = "gui"),
+        paused: false,
+        out: None,
+        format: Format::Png,
+        every: 1,
+        scale: 2,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(Error::Usage);
+        match arg.as_str() {
+            "--term" => opts.windowed = false,
+            "--paused" => opts.paused = true,
+            "--out" => opts.out = Some(PathBuf::from(value()?)),
+            "--format" => opts.format = value()?.parse().map_err(|_| Error::Usage)?,
+            "--every" => opts.every = value()?.parse().map_err(|_| Error::Usage)?,
+            "--scale" => opts.scale = value()?.parse().map_err(|_| Error::Usage)?,
+            _ if !arg.starts_with("--") => opts.input = PathBuf::from(arg),
+            _ => return Err(Error::Usage),
+        }
+    }
+    if opts.every == 0 || opts.scale == 0 {
+        return Err(Error::Usage);
+    }
+    if opts.out.as_ref().is_some_and(|out| out.extension().is_some_and(|ext| ext == "gif")) {
+        opts.format = Format::Gif;
+    }
+    Ok(opts)
+}
+
+fn export(viz: &Viz, input: &str, opts: &Options, out: PathBuf) -> Result<(), Error> {
+    let buffer = empty_buffer(input);
+    let mut recorder = Recorder::new(buffer, out, opts.format, opts.every, opts.scale, viz.delay)?;
+    (viz.run)(input, &mut Painter(&mut recorder));
+    let frames = recorder.finish()?;
+    eprintln!("wrote {} frames", frames);
+    Ok(())
+}
+
+fn main() {
+    let opts = parse_options(std::env::args().skip(1)).unwrap_or_else(|err| die(err));
+    let viz = VIZS
+        .iter()
+        .find(|viz| viz.day == opts.day)
+        .unwrap_or_else(|| die(Error::UnknownDay(opts.day.to_string())));
+    let input = std::fs::read_to_string(&opts.input).unwrap_or_else(|err| die(err));
+    if let Some(out) = opts.out.clone() {
+        return export(viz, &input, &opts, out).unwrap_or_else(|err| die(err));
+    }
+    let live = Arc::new(Live { viz, remote: Remote::new(viz.delay, opts.paused) });
+    let screen = Screen::new(&input);
+    let (tx, rx) = mpsc::sync_channel(screen::QUEUE);
+    // keys can only be read from a terminal that can be put in raw mode
+    let raw = if opts.windowed { None } else { term::RawMode::enable() };
+    let interactive = opts.windowed || raw.is_some();
+    let solver = {
+        let live = live.clone();
+        thread::spawn(move || solve(&live, &input, tx, interactive))
+    };
+    if opts.windowed {
+        #[cfg(feature = "gui")]
+        gui::run(live, screen, rx).unwrap_or_else(|err| die(err));
+    } else {
+        term::run(&live, screen, &rx, &solver, raw).unwrap_or_else(|err| die(err));
+    }
+}
@@ -0,0 +1,122 @@
+use super::{empty_buffer, Buffer, Live, Pos, Sprite, EMPTY};
+use std::sync::mpsc::Receiver;
+
+// how many events the solver can get ahead of the renderer before it blocks
+pub const QUEUE: usize = 1 << 16;
+
+// what the solver thread sends the renderer
+pub enum Event {
+    Reset,
+    Draw(Pos, Sprite),
+    Frame,
+}
+
+// the renderer's own copy of the cells, plus how much of each color there is
+// and which cells changed since they were last drawn
+pub struct Screen {
+    pub cells: Buffer,
+    counts: [usize; 7],
+    frames: usize,
+    marked: Vec<Vec<bool>>,
+    dirty: Vec<Pos>,
+    redraw: bool,
+}
+
+impl Screen {
+    pub fn new(input: &str) -> Self {
+        let cells = empty_buffer(input);
+        let marked = vec![vec![false; cells[0].len()]; cells.len()];
+        let mut screen =
+            Screen { cells, counts: [0; 7], frames: 0, marked, dirty: Vec::new(), redraw: true };
+        screen.reset();
+        screen
+    }
+
+    fn reset(&mut self) {
+        self.cells.iter_mut().for_each(|row| row.fill(EMPTY));
+        self.counts = [0; 7];
+        self.counts[EMPTY.color as usize] = self.cells.len() * self.cells[0].len();
+        self.frames = 0;
+        self.redraw = true;
+    }
+
+    fn apply(&mut self, event: Event) {
+        match event {
+            Event::Reset => self.reset(),
+            Event::Draw((row, col), sprite) => {
+                let old = std::mem::replace(&mut self.cells[row][col], sprite);
+                self.counts[old.color as usize] -= 1;
+                self.counts[sprite.color as usize] += 1;
+                if !self.marked[row][col] {
+                    self.marked[row][col] = true;
+                    self.dirty.push((row, col));
+                }
+            }
+            Event::Frame => self.frames += 1,
+        }
+    }
+
+    // takes at most a queue's worth, so a fast solver can't starve the renderer
+    pub fn drain(&mut self, events: &Receiver<Event>) {
+        for event in events.try_iter().take(QUEUE) {
+            self.apply(event);
+        }
+    }
+
+    // the cells changed since the last call, or None if everything has to be
+    // drawn again
+    pub fn take_dirty(&mut self) -> Option<Vec<Pos>> {
+        for &(row, col) in &self.dirty {
+            self.marked[row][col] = false;
+        }
+        let dirty = std::mem::take(&mut self.dirty);
+        if std::mem::take(&mut self.redraw) {
+            None
+        } else {
+            Some(dirty)
+        }
+    }
+
+    // how many cells there are of each of the day's colors, with its label
+    pub fn counters(&self, live: &Live) -> Vec<String> {
+        let counter = |(label, colors): &(&str, &[_])| {
+            let count: usize = colors.iter().map(|color| self.counts[*color as usize]).sum();
+            format!("{} {}", label, count)
+        };
+        live.viz.counters.iter().map(counter).collect()
+    }
+
+    pub fn status(&self, live: &Live) -> String {
+        let mut status = format!("{} | frame {}", live.viz.day, self.frames);
+        for counter in self.counters(live) {
+            status += &format!(" | {}", counter);
+        }
+        format!("{} | {}", status, live.remote.status())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{sprite, Color};
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_dirty() {
+        let (tx, rx) = mpsc::sync_channel(QUEUE);
+        let mut screen = Screen::new("...\n...\n");
+        assert_eq!(screen.take_dirty(), None);
+        let red = sprite('█', Color::Red);
+        for event in [Event::Draw((0, 1), red), Event::Frame, Event::Draw((0, 1), red)] {
+            tx.send(event).unwrap();
+        }
+        screen.drain(&rx);
+        assert_eq!(screen.take_dirty(), Some(vec![(0, 1)]));
+        assert_eq!(screen.take_dirty(), Some(vec![]));
+        assert_eq!((screen.counts[Color::Red as usize], screen.frames), (1, 1));
+        tx.send(Event::Reset).unwrap();
+        screen.drain(&rx);
+        assert_eq!(screen.take_dirty(), None);
+        assert_eq!(screen.counts[Color::Red as usize], 0);
+    }
+}
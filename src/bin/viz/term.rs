@@ -0,0 +1,182 @@
+use super::controls::Key;
+use super::screen::{Event, Screen};
+use super::{Buffer, Color, Live, Pos, Sprite, EMPTY};
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const FRAME_MILLIS: u64 = 33;
+
+const CLEAR: &str = "\x1b[2J";
+const CLEAR_LINE: &str = "\x1b[K";
+const HOME: &str = "\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET: &str = "\x1b[0m";
+
+const HELP: &str = "space pause | . step | +/- speed | r restart | q quit";
+
+impl Color {
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Black => RESET,
+            Color::Gray => "\x1b[90m",
+            Color::White => "\x1b[97m",
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Blue => "\x1b[34m",
+            Color::Yellow => "\x1b[33m",
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// unbuffered, unechoed keys for as long as this is alive
+pub struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    pub fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+// how many cells go into each character so that the grid fits the terminal,
+// leaving a line for the status
+fn cells_per_char(data: &Buffer) -> usize {
+    let size = stty(&["size"]).and_then(|size| {
+        let (rows, cols) = size.split_once(' ')?;
+        Some((rows.parse::<usize>().ok()?, cols.parse::<usize>().ok()?))
+    });
+    match size {
+        Some((rows, cols)) if rows > 1 && cols > 0 => {
+            data.len().div_ceil(rows - 1).max(data[0].len().div_ceil(cols)).max(1)
+        }
+        _ => 1,
+    }
+}
+
+// a block of cells shows its most prominent color
+fn block(data: &Buffer, row: usize, col: usize, k: usize) -> Sprite {
+    if k == 1 {
+        return data[row][col];
+    }
+    let rows = &data[row..(row + k).min(data.len())];
+    let color = rows
+        .iter()
+        .flat_map(|cells| &cells[col..(col + k).min(cells.len())])
+        .map(|sprite| sprite.color)
+        .max()
+        .unwrap_or(EMPTY.color);
+    if color == EMPTY.color {
+        EMPTY
+    } else {
+        Sprite { glyph: '█', color }
+    }
+}
+
+// only emits a color escape when the color changes, to keep the frames small
+fn render_all(data: &Buffer, k: usize, frame: &mut String) {
+    frame.push_str(HOME);
+    for row in (0..data.len()).step_by(k) {
+        let mut color = Color::Black;
+        for col in (0..data[row].len()).step_by(k) {
+            let sprite = block(data, row, col, k);
+            if sprite.color != color {
+                color = sprite.color;
+                frame.push_str(color.ansi());
+            }
+            frame.push(sprite.glyph);
+        }
+        writeln!(frame, "{}", RESET).unwrap();
+    }
+}
+
+// moves the cursor to each block holding a dirty cell and redraws just that
+fn render_dirty(data: &Buffer, k: usize, mut dirty: Vec<Pos>, frame: &mut String) {
+    dirty.iter_mut().for_each(|(row, col)| (*row, *col) = (*row / k * k, *col / k * k));
+    dirty.sort_unstable();
+    dirty.dedup();
+    for (row, col) in dirty {
+        let sprite = block(data, row, col, k);
+        write!(
+            frame,
+            "\x1b[{};{}H{}{}",
+            row / k + 1,
+            col / k + 1,
+            sprite.color.ansi(),
+            sprite.glyph
+        )
+        .unwrap();
+    }
+    frame.push_str(RESET);
+}
+
+fn render(screen: &mut Screen, k: usize, frame: &mut String) {
+    frame.clear();
+    match screen.take_dirty() {
+        None => render_all(&screen.cells, k, frame),
+        Some(dirty) => render_dirty(&screen.cells, k, dirty, frame),
+    }
+    // the status goes on the line below the grid
+    write!(frame, "\x1b[{};1H{}", screen.cells.len().div_ceil(k) + 1, RESET).unwrap();
+}
+
+fn read_keys(live: Arc<Live>) {
+    for byte in io::stdin().lock().bytes() {
+        let Ok(byte) = byte else { return };
+        if let Some(key) = Key::from_char(byte as char) {
+            live.remote.press(key);
+        }
+    }
+}
+
+pub fn run(
+    live: &Arc<Live>,
+    mut screen: Screen,
+    events: &Receiver<Event>,
+    solver: &JoinHandle<()>,
+    raw: Option<RawMode>,
+) -> io::Result<()> {
+    if raw.is_some() {
+        let live = live.clone();
+        thread::spawn(move || read_keys(live));
+    }
+    let k = cells_per_char(&screen.cells);
+    let mut stdout = io::stdout().lock();
+    let mut frame = String::new();
+    write!(stdout, "{}{}", CLEAR, HIDE_CURSOR)?;
+    loop {
+        let done = solver.is_finished();
+        screen.drain(events);
+        render(&mut screen, k, &mut frame);
+        let mut status = screen.status(live);
+        if raw.is_some() {
+            status = format!("{} | {}", status, HELP);
+        }
+        write!(stdout, "{}{}{}", frame, status, CLEAR_LINE)?;
+        stdout.flush()?;
+        if done {
+            break;
+        }
+        thread::sleep(Duration::from_millis(FRAME_MILLIS));
+    }
+    writeln!(stdout, "{}", SHOW_CURSOR)
+}
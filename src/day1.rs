@@ -1,18 +1,26 @@
+use crate::static_vec::StaticVec;
+
+pub type Lines<'a> = StaticVec<&'a str, 1024>;
+
+pub fn parse(input: &str) -> Lines<'_> {
+    input.lines().collect()
+}
+
 fn calibration_sum(
-    input: &str,
+    lines: &Lines,
     first: impl Fn(&str) -> u32,
     last: impl Fn(&str) -> u32,
 ) -> u32 {
-    input.lines().map(|line| first(line) * 10 + last(line)).sum()
+    lines.iter().map(|line| first(line) * 10 + last(line)).sum()
 }
 
 fn find_ascii_digit(mut line: impl Iterator<Item = char>) -> u32 {
     line.find_map(|ch| ch.to_digit(10)).unwrap()
 }
 
-pub fn part1(input: &str) -> u32 {
+pub fn part1(lines: &Lines) -> u32 {
     calibration_sum(
-        input,
+        lines,
         |line| find_ascii_digit(line.chars()),
         |line| find_ascii_digit(line.chars().rev()),
     )
@@ -36,9 +44,9 @@ fn find_digit(line: &str, range: impl Iterator<Item = usize>) -> u32 {
     panic!("digit not found");
 }
 
-pub fn part2(input: &str) -> u32 {
+pub fn part2(lines: &Lines) -> u32 {
     calibration_sum(
-        input,
+        lines,
         |line| find_digit(line, 0..line.len()),
         |line| find_digit(line, (0..line.len()).rev()),
     )
@@ -56,7 +64,7 @@ a1b2c3d4e5f
 treb7uchet
 ";
         let expected = 142;
-        let actual = part1(input);
+        let actual = part1(&parse(input));
         assert_eq!(expected, actual);
     }
 
@@ -71,16 +79,7 @@ zoneight234
 7pqrstsixteen
 ";
         let expected = 281;
-        let actual = part2(input);
+        let actual = part2(&parse(input));
         assert_eq!(expected, actual);
     }
-
-    #[test]
-    fn test_solution() {
-        const INPUT: &str = include_str!("../inputs/day1.txt");
-        let actual1 = part1(INPUT);
-        assert_eq!(54927, actual1);
-        let actual2 = part2(INPUT);
-        assert_eq!(54581, actual2);
-    }
 }
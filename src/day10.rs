@@ -1,5 +1,6 @@
 use crate::static_queue::StaticQueue;
 use crate::static_vec::StaticVec;
+use crate::viz::{Observer, Pos};
 use heapless::FnvIndexSet;
 
 type Tile = u8;
@@ -7,6 +8,17 @@ type Pt2 = (u8, u8);
 type Set<T> = FnvIndexSet<T, 16384>;
 type Queue<T> = StaticQueue<T, 16384>;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cell {
+    Loop(Tile),
+    Walk(Tile),
+    Interior,
+}
+
+fn pos((row, col): Pt2) -> Pos {
+    (row as usize, col as usize)
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 enum Dir {
     #[default]
@@ -27,10 +39,11 @@ fn go((row, col): Pt2, dir: Dir) -> Option<Pt2> {
 }
 
 #[derive(Debug)]
-struct Grid<'a> {
+pub struct Grid<'a> {
     data: &'a [u8],
     width: u8,
     height: u8,
+    start: Pt2,
 }
 
 impl Grid<'_> {
@@ -84,23 +97,31 @@ fn find(grid: &Grid, tile: Tile) -> Option<Pt2> {
     None
 }
 
-fn find_loop(grid: &Grid, start: Pt2, v: &mut Set<Pt2>) {
+fn find_loop(grid: &Grid, start: Pt2, v: &mut Set<Pt2>, obs: &mut impl Observer<Cell>) {
     let mut q = Queue::new();
     q.push_back((start, 0));
     v.insert(start).unwrap();
+    obs.cell_changed(pos(start), Cell::Loop(grid.at(start).unwrap()));
     while let Some(front @ (cur, dist)) = q.pop_front() {
         let nbrs = tube_connections(grid, cur);
         for nbr in nbrs {
             if q.front() == Some(&front) {
                 v.insert(nbr).unwrap();
+                obs.cell_changed(pos(nbr), Cell::Loop(grid.at(nbr).unwrap()));
+                obs.frame();
                 return;
             }
             if v.contains(&nbr) {
                 continue;
             }
             v.insert(nbr).unwrap();
+            obs.cell_changed(pos(nbr), Cell::Loop(grid.at(nbr).unwrap()));
             q.push_back((nbr, dist + 1));
         }
+        obs.frame();
+        if obs.stopped() {
+            return;
+        }
     }
 }
 
@@ -122,27 +143,42 @@ fn interior_neighbors(grid: &Grid, prev: Pt2, cur: Pt2) -> StaticVec<Pt2, 4> {
     }
 }
 
-fn explore(looop: &Set<Pt2>, from: Pt2, v: &mut Set<Pt2>) {
-    for dir in [Dir::Left, Dir::Right, Dir::Above, Dir::Below] {
-        if let Some(nbr) = go(from, dir) {
-            if v.contains(&nbr) || looop.contains(&nbr) {
-                continue;
+// depth first, with a stack of its own since the interior can fill the grid
+fn explore(looop: &Set<Pt2>, from: Pt2, v: &mut Set<Pt2>, obs: &mut impl Observer<Cell>) {
+    let mut stack = StaticVec::<Pt2, 16384>::empty();
+    stack.push(from);
+    while !stack.is_empty() {
+        let cur = stack.pop();
+        for dir in [Dir::Left, Dir::Right, Dir::Above, Dir::Below] {
+            if let Some(nbr) = go(cur, dir) {
+                if v.contains(&nbr) || looop.contains(&nbr) {
+                    continue;
+                }
+                v.insert(nbr).unwrap();
+                obs.cell_changed(pos(nbr), Cell::Interior);
+                obs.frame();
+                if obs.stopped() {
+                    return;
+                }
+                stack.push(nbr);
             }
-            v.insert(nbr).unwrap();
-            explore(looop, nbr, v);
         }
     }
 }
 
-fn interior_area(grid: &Grid, looop: &Set<Pt2>) -> i32 {
+fn interior_area(grid: &Grid, looop: &Set<Pt2>, obs: &mut impl Observer<Cell>) -> i32 {
     let start = *looop.iter().min_by(|(r1, c1), (r2, c2)| r1.cmp(r2).then(c1.cmp(c2))).unwrap();
     let mut v = Set::new();
     let (mut prev, mut cur) = (start, start);
-    while cur != start || prev == start {
+    while (cur != start || prev == start) && !obs.stopped() {
+        obs.cell_changed(pos(cur), Cell::Walk(grid.at(cur).unwrap()));
+        obs.frame();
         for pt in interior_neighbors(grid, prev, cur) {
             if !v.contains(&pt) && !looop.contains(&pt) {
                 v.insert(pt).unwrap();
-                explore(looop, pt, &mut v);
+                obs.cell_changed(pos(pt), Cell::Interior);
+                obs.frame();
+                explore(looop, pt, &mut v, obs);
             }
         }
         let nbrs = tube_connections(grid, cur);
@@ -152,26 +188,50 @@ fn interior_area(grid: &Grid, looop: &Set<Pt2>) -> i32 {
     v.len() as i32
 }
 
-fn parse(input: &str) -> Grid {
+pub fn parse(input: &str) -> Grid<'_> {
     let width = input.lines().next().unwrap().len() as u8;
     let height = input.lines().count() as u8;
-    Grid { data: input.as_bytes(), width, height }
+    let mut grid = Grid { data: input.as_bytes(), width, height, start: (0, 0) };
+    grid.start = find(&grid, b'S').unwrap();
+    grid
 }
 
-pub fn part1(input: &str) -> i32 {
-    let grid = parse(input);
-    let start = find(&grid, b'S').unwrap();
+pub fn part1(grid: &Grid) -> i32 {
     let mut looop = Set::new();
-    find_loop(&grid, start, &mut looop);
+    find_loop(grid, grid.start, &mut looop, &mut ());
     looop.len() as i32 / 2
 }
 
-pub fn part2(input: &str) -> i32 {
-    let grid = parse(input);
-    let start = find(&grid, b'S').unwrap();
+pub fn part2_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> i32 {
     let mut looop = Set::new();
-    find_loop(&grid, start, &mut looop);
-    interior_area(&grid, &looop)
+    find_loop(grid, grid.start, &mut looop, obs);
+    if obs.stopped() {
+        return 0;
+    }
+    interior_area(grid, &looop, obs)
+}
+
+pub fn part2(grid: &Grid) -> i32 {
+    part2_observed(grid, &mut ())
+}
+
+// walks the loop once, getting its area from the shoelace formula, and then
+// by pick's theorem the tiles inside are what's left after the boundary
+pub fn part2_shoelace(grid: &Grid) -> i32 {
+    let start = grid.start;
+    let (mut prev, mut cur) = (start, tube_connections(grid, start)[0]);
+    let (mut twice_area, mut boundary) = (0, 1);
+    loop {
+        let ((r0, c0), (r1, c1)) = (prev, cur);
+        twice_area += c0 as i32 * r1 as i32 - c1 as i32 * r0 as i32;
+        if cur == start {
+            break;
+        }
+        let next = tube_connections(grid, cur).into_iter().find(|nbr| *nbr != prev).unwrap();
+        (prev, cur) = (cur, next);
+        boundary += 1;
+    }
+    twice_area.abs() / 2 - boundary / 2 + 1
 }
 
 #[cfg(test)]
@@ -186,7 +246,7 @@ L|7||
 -L-J|
 L|-JF
 ";
-        assert_eq!(part1(input), 4);
+        assert_eq!(part1(&parse(input)), 4);
     }
 
     #[test]
@@ -197,7 +257,7 @@ SJLL7
 |F--J
 LJ.LJ
 ";
-        assert_eq!(part1(input), 8);
+        assert_eq!(part1(&parse(input)), 8);
     }
 
     #[test]
@@ -212,7 +272,9 @@ LJ.LJ
 .L--JOL--J.
 .....O.....
 ";
-        assert_eq!(part2(input), 4);
+        let grid = parse(input);
+        assert_eq!(part2(&grid), 4);
+        assert_eq!(part2_shoelace(&grid), 4);
     }
 
     #[test]
@@ -227,7 +289,9 @@ LJ.LJ
 .L--JL--J.
 ..........
 ";
-        assert_eq!(part2(input), 4);
+        let grid = parse(input);
+        assert_eq!(part2(&grid), 4);
+        assert_eq!(part2_shoelace(&grid), 4);
     }
 
     #[test]
@@ -243,7 +307,9 @@ L--J.L7...LJS7F-7L7.
 ....FJL-7.||.||||...
 ....L---J.LJ.LJLJ...
 ";
-        assert_eq!(part2(input), 8);
+        let grid = parse(input);
+        assert_eq!(part2(&grid), 8);
+        assert_eq!(part2_shoelace(&grid), 8);
     }
 
     #[test]
@@ -259,13 +325,49 @@ L---JF-JLJ.||-FJLJJ7
 L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L
 ";
-        assert_eq!(part2(input), 10);
+        let grid = parse(input);
+        assert_eq!(part2(&grid), 10);
+        assert_eq!(part2_shoelace(&grid), 10);
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day10.txt");
-        assert_eq!(part1(input), 7102);
-        assert_eq!(part2(input), 363);
+    fn test_observer() {
+        struct Counter {
+            interior: usize,
+            frames: usize,
+            stop_at: usize,
+        }
+        impl Observer<Cell> for Counter {
+            fn cell_changed(&mut self, _: Pos, cell: Cell) {
+                if cell == Cell::Interior {
+                    self.interior += 1;
+                }
+            }
+            fn frame(&mut self) {
+                self.frames += 1;
+            }
+            fn stopped(&self) -> bool {
+                self.frames == self.stop_at
+            }
+        }
+        let input = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+";
+        let grid = parse(input);
+        let mut counter = Counter { interior: 0, frames: 0, stop_at: usize::MAX };
+        assert_eq!(part2_observed(&grid, &mut counter), 4);
+        assert_eq!(counter.interior, 4);
+        assert!(counter.frames > 3);
+        // stopping leaves out every frame after
+        let mut counter = Counter { interior: 0, frames: 0, stop_at: 3 };
+        part2_observed(&grid, &mut counter);
+        assert_eq!(counter.frames, 3);
     }
 }
@@ -1,39 +1,43 @@
+use crate::params::{self, Error};
 use crate::static_vec::StaticVec;
 
-#[derive(Clone, Copy, PartialEq, Default, Debug)]
-enum Tile {
-    #[default]
-    Empty,
-    Galaxy,
-}
-
 type Pt2 = (u16, u16);
-type Grid<'a> = StaticVec<StaticVec<Tile, 256>, 256>;
 type Weights = (StaticVec<i64, 256>, StaticVec<i64, 256>);
 
-fn parse(input: &str, grid: &mut Grid) {
+// where the galaxies are, and which rows and columns have none
+pub struct Image {
+    galaxies: StaticVec<Pt2, 4096>,
+    empty_rows: StaticVec<bool, 256>,
+    empty_cols: StaticVec<bool, 256>,
+}
+
+pub fn parse(input: &str) -> Image {
+    let width = input.lines().next().map_or(0, str::len);
+    let mut image = Image {
+        galaxies: StaticVec::empty(),
+        empty_rows: StaticVec::empty(),
+        empty_cols: (0..width).map(|_| true).collect(),
+    };
     for (i, line) in input.lines().enumerate() {
-        grid.push(StaticVec::empty());
-        for b in line.bytes() {
+        image.empty_rows.push(true);
+        for (j, b) in line.bytes().enumerate() {
             match b {
-                b'.' => grid[i].push(Tile::Empty),
-                b'#' => grid[i].push(Tile::Galaxy),
+                b'.' => {}
+                b'#' => {
+                    image.galaxies.push((i as u16, j as u16));
+                    image.empty_rows[i] = false;
+                    image.empty_cols[j] = false;
+                }
                 _ => panic!("invalid tile"),
             }
         }
     }
+    image
 }
 
-fn expand(grid: &Grid, multiplier: i64) -> Weights {
-    let row_weights = grid
-        .iter()
-        .map(|row| if row.iter().all(|t| *t == Tile::Empty) { multiplier } else { 1 })
-        .collect();
-    let is_col_empty = |j: usize| (0..grid.len()).all(|i| grid[i][j] == Tile::Empty);
-    let col_weights = (0..grid[0].len())
-        .map(|j| if is_col_empty(j) { multiplier } else { 1 })
-        .collect();
-    (row_weights, col_weights)
+fn expand(image: &Image, multiplier: i64) -> Weights {
+    let weight = |empty: &bool| if *empty { multiplier } else { 1 };
+    (image.empty_rows.iter().map(weight).collect(), image.empty_cols.iter().map(weight).collect())
 }
 
 fn shortest_path(weights: &Weights, from: Pt2, to: Pt2) -> i64 {
@@ -46,19 +50,9 @@ fn shortest_path(weights: &Weights, from: Pt2, to: Pt2) -> i64 {
     row_dist + col_dist
 }
 
-fn sum_shortest_paths(input: &str, multiplier: i64) -> i64 {
-    let mut grid = Grid::empty();
-    parse(input, &mut grid);
-    let weights = expand(&grid, multiplier);
-    let mut galaxies = StaticVec::<Pt2, 4096>::empty();
-    for i in 0..grid.len() {
-        for j in 0..grid[i].len() {
-            if grid[i][j] == Tile::Galaxy {
-                let from = (i as u16, j as u16);
-                galaxies.push(from);
-            }
-        }
-    }
+fn sum_shortest_paths(image: &Image, multiplier: i64) -> i64 {
+    let weights = expand(image, multiplier);
+    let galaxies = &image.galaxies;
     let mut sum = 0;
     for i in 0..galaxies.len() - 1 {
         let from = galaxies[i];
@@ -70,12 +64,40 @@ fn sum_shortest_paths(input: &str, multiplier: i64) -> i64 {
     sum
 }
 
-pub fn part1(input: &str) -> i64 {
-    sum_shortest_paths(input, 2)
+pub fn part1(image: &Image) -> i64 {
+    sum_shortest_paths(image, 2)
+}
+
+// how many rows or columns each empty one becomes in part 2
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub expansion: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { expansion: 1000000 }
+    }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["expansion"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "expansion" => self.expansion = params::parse(value)?,
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
+    }
 }
 
-pub fn part2(input: &str) -> i64 {
-    sum_shortest_paths(input, 1000000)
+pub fn part2_with(image: &Image, params: &Params) -> i64 {
+    sum_shortest_paths(image, params.expansion)
+}
+
+pub fn part2(image: &Image) -> i64 {
+    part2_with(image, &Params::default())
 }
 
 #[cfg(test)]
@@ -95,13 +117,9 @@ mod test {
 .......#..
 #...#.....
 ";
-        assert_eq!(part1(input), 374);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day11.txt");
-        assert_eq!(part1(input), 9609130);
-        assert_eq!(part2(input), 702152204842);
+        let image = parse(input);
+        assert_eq!(part1(&image), 374);
+        assert_eq!(part2_with(&image, &Params { expansion: 10 }), 1030);
+        assert_eq!(part2_with(&image, &Params { expansion: 100 }), 8410);
     }
 }
@@ -1,7 +1,12 @@
+use crate::params::{self, Error};
 use crate::static_vec::StaticVec;
 
 type Vec<T> = StaticVec<T, 128>;
-type Matrix<T> = Vec<Vec<T>>;
+const MAX: usize = 128;
+// the springs in a row before it's unfolded
+const MAX_ROW: usize = 20;
+// the most copies of the longest row that fit in `MAX` once joined
+const MAX_COPIES: usize = (MAX + 1) / (MAX_ROW + 1);
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 enum Spring {
@@ -22,77 +27,62 @@ impl From<u8> for Spring {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Default)]
-enum Outcome {
-    #[default]
-    None,
-    Invalid,
-    Valid(i64),
-}
-
-impl Outcome {
-    fn unwrap_or(&self, value: i64) -> i64 {
-        if let Self::Valid(outcome) = self {
-            *outcome
-        } else {
-            value
+// ways[i] is how many ways the groups so far fit into the first i springs,
+// kept for one more group at a time. a group can end just before spring i if
+// none of the springs it covers are working ones, which the prefix counts of
+// working springs answer at once, and the spring before it isn't broken.
+fn arrangements(springs: &[Spring], lens: &[usize]) -> i64 {
+    let n = springs.len();
+    let mut working = [0; MAX + 1];
+    for (i, spring) in springs.iter().enumerate() {
+        working[i + 1] = working[i] + (*spring == Spring::Ok) as usize;
+    }
+    let mut ways = [0; MAX + 1];
+    ways[0] = 1;
+    for i in 1..=n {
+        ways[i] = if springs[i - 1] == Spring::Broken { 0 } else { ways[i - 1] };
+    }
+    // a group can't end before the ones up to it fit, or after the ones left
+    // no longer would
+    let (mut lo, mut hi) = (0, n + 1 - lens.iter().map(|len| len + 1).sum::<usize>());
+    let mut next = [0; MAX + 1];
+    for (g, &len) in lens.iter().enumerate() {
+        lo += len + (g > 0) as usize;
+        hi += len + 1;
+        next[..lo].fill(0);
+        for i in lo..hi.min(n + 1) {
+            let start = i - len;
+            let fits = working[i] == working[start]
+                && (start == 0 || springs[start - 1] != Spring::Broken);
+            let before = if fits { ways[start.saturating_sub(1)] } else { 0 };
+            let skip = if springs[i - 1] != Spring::Broken { next[i - 1] } else { 0 };
+            next[i] = before + skip;
         }
+        core::mem::swap(&mut ways, &mut next);
     }
+    ways[n]
 }
 
-fn arrangements_memoized(springs: &[Spring], lens: &[usize]) -> i64 {
-    let mut m = Matrix::of(Vec::from([Outcome::None; 128]));
-    place(springs, lens, &mut m).unwrap_or(0)
-}
-
-// count how many ways we can place |len| broken springs at the *beginning* of
-// the array |springs| and the remaining |lens| of broken spring lengths anywhere
-// in the remaining array
-fn place_here(len: usize, springs: &[Spring], lens: &[usize], m: &mut Matrix<Outcome>) -> Outcome {
-    // try to place |len| broken springs
-    // then, make sure we can now skip a working spring
-    if len > springs.len() || springs[..len].iter().any(|spring| *spring == Spring::Ok) {
-        Outcome::Invalid
-    } else if len >= springs.len() {
-        place(&springs[len..], lens, m)
-    } else if springs[len] == Spring::Broken {
-        Outcome::Invalid
-    } else {
-        place(&springs[len + 1..], lens, m)
-    }
+// a row's springs and the lengths of its groups of broken ones, which can't
+// outnumber the springs
+#[derive(Clone, Copy, Default)]
+pub struct Row {
+    springs: StaticVec<Spring, MAX_ROW>,
+    lens: StaticVec<usize, { MAX_ROW / 2 }>,
 }
 
-// count how many ways we can place strings of broken strings of lengths |lens| into
-// the array |springs|
-fn place(springs: &[Spring], lens: &[usize], m: &mut Matrix<Outcome>) -> Outcome {
-    if let memo @ (Outcome::Valid(_) | Outcome::Invalid) = m[springs.len()][lens.len()] {
-        return memo;
-    }
-    let outcome = match (springs.iter().next(), lens.iter().next()) {
-        (None, None) => Outcome::Valid(1),
-        (None, Some(_)) => Outcome::Invalid,
-        (Some(Spring::Ok), _) => place(&springs[1..], lens, m),
-        (Some(Spring::Broken), None) => Outcome::Invalid,
-        (Some(Spring::Broken), Some(len)) => place_here(*len, springs, &lens[1..], m),
-        (Some(Spring::Unknown), None) => place(&springs[1..], lens, m),
-        (Some(Spring::Unknown), Some(len)) => {
-            let here = place_here(*len, springs, &lens[1..], m).unwrap_or(0);
-            let there = place(&springs[1..], lens, m).unwrap_or(0);
-            Outcome::Valid(here + there)
-        }
-    };
-    m[springs.len()][lens.len()] = outcome;
-    outcome
-}
+pub type Rows = StaticVec<Row, 1024>;
 
-fn parse(line: &str, springs: &mut Vec<Spring>, lens: &mut Vec<usize>) {
+fn parse_row(line: &str) -> Row {
     let (lhs, rhs) = line.split_once(' ').unwrap();
-    for spring in lhs.bytes().map(Spring::from) {
-        springs.push(spring);
-    }
-    for len in rhs.split(',').map(|len| len.parse::<usize>().unwrap()) {
-        lens.push(len);
-    }
+    assert!(lhs.len() <= MAX_ROW, "row too long");
+    let springs = lhs.bytes().map(Spring::from).collect();
+    let lens = rhs.split(',').map(|len| len.parse::<usize>().unwrap()).collect();
+    Row { springs, lens }
+}
+
+pub fn parse(input: &str) -> Rows {
+    input.lines().map(parse_row).collect()
 }
 
 fn expand(by: usize, springs: &mut Vec<Spring>, lens: &mut Vec<usize>) {
@@ -109,31 +99,65 @@ fn expand(by: usize, springs: &mut Vec<Spring>, lens: &mut Vec<usize>) {
     }
 }
 
-fn sum_arrangements(input: &str, copies: usize) -> i64 {
+fn unfold(row: &Row, copies: usize) -> (Vec<Spring>, Vec<usize>) {
+    let mut springs = row.springs.iter().copied().collect();
+    let mut lens = row.lens.iter().copied().collect();
+    expand(copies, &mut springs, &mut lens);
+    (springs, lens)
+}
+
+fn sum_arrangements(rows: &Rows, copies: usize) -> i64 {
     let mut sum = 0;
-    let mut springs = Vec::empty();
-    let mut lens = Vec::empty();
-    for line in input.lines() {
-        springs.clear();
-        lens.clear();
-        parse(line, &mut springs, &mut lens);
-        expand(copies, &mut springs, &mut lens);
-        sum += arrangements_memoized(&springs[..], &lens[..]);
+    for row in rows.iter() {
+        let (springs, lens) = unfold(row, copies);
+        sum += arrangements(&springs[..], &lens[..]);
     }
     sum
 }
 
-pub fn part1(input: &str) -> i64 {
-    sum_arrangements(input, 1)
+pub fn part1(rows: &Rows) -> i64 {
+    sum_arrangements(rows, 1)
+}
+
+// how many copies of each row are joined to unfold it in part 2
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub copies: usize,
 }
 
-pub fn part2(input: &str) -> i64 {
-    sum_arrangements(input, 5)
+impl Default for Params {
+    fn default() -> Self {
+        Params { copies: 5 }
+    }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["copies"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "copies" => match params::parse(value)? {
+                copies @ 1..=MAX_COPIES => self.copies = copies,
+                _ => return Err(Error::Invalid),
+            },
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
+    }
+}
+
+pub fn part2_with(rows: &Rows, params: &Params) -> i64 {
+    sum_arrangements(rows, params.copies)
+}
+
+pub fn part2(rows: &Rows) -> i64 {
+    part2_with(rows, &Params::default())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen;
 
     #[test]
     fn test_example() {
@@ -144,14 +168,65 @@ mod test {
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1
 ";
-        assert_eq!(part1(input), 21);
-        assert_eq!(part2(input), 525152);
+        let rows = parse(input);
+        assert_eq!(part1(&rows), 21);
+        assert_eq!(part2(&rows), 525152);
+        assert_eq!(part2_with(&rows, &Params { copies: 1 }), 21);
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day12.txt");
-        assert_eq!(part1(input), 8419);
-        assert_eq!(part2(input), 160500973317706);
+    fn test_copies() {
+        use crate::params::Params as _;
+        let mut params = Params::default();
+        for value in ["0", "7", "100"] {
+            assert_eq!(params.set("copies", value), Err(Error::Invalid));
+        }
+        assert_eq!(params.set("copies", "6"), Ok(()));
+        // the longest row still fits unfolded that many times
+        let rows = parse("???????????????????? 1\n");
+        assert!(part2_with(&rows, &params) > 0);
+    }
+
+    // every way of filling in the unknown springs, and whether its runs of
+    // broken ones are the groups
+    fn brute_force(springs: &[Spring], lens: &[usize]) -> i64 {
+        let unknown = springs.iter().filter(|spring| **spring == Spring::Unknown).count();
+        let mut count = 0;
+        for fill in 0..1u32 << unknown {
+            let (mut runs, mut run, mut bit) = (Vec::<usize>::empty(), 0, 0);
+            for spring in springs.iter().chain([Spring::Ok].iter()) {
+                let broken = match spring {
+                    Spring::Unknown => {
+                        bit += 1;
+                        fill >> (bit - 1) & 1 == 1
+                    }
+                    spring => *spring == Spring::Broken,
+                };
+                if broken {
+                    run += 1;
+                } else if run > 0 {
+                    runs.push(run);
+                    run = 0;
+                }
+            }
+            count += (runs[..] == *lens) as i64;
+        }
+        count
+    }
+
+    #[test]
+    fn test_brute_force() {
+        for seed in 0..8 {
+            for line in gen::input(12, seed, 40).lines() {
+                for copies in [1, 2] {
+                    let (springs, lens) = unfold(&parse_row(line), copies);
+                    if springs.iter().filter(|spring| **spring == Spring::Unknown).count() > 14 {
+                        continue;
+                    }
+                    let expected = brute_force(&springs[..], &lens[..]);
+                    assert_eq!(arrangements(&springs[..], &lens[..]), expected, "{}", line);
+                }
+            }
+        }
     }
 }
@@ -1,50 +1,60 @@
 use crate::static_vec::StaticVec;
 
-type Grid = StaticVec<StaticVec<u8, 32>, 32>;
+type Lines = StaticVec<u32, 32>;
 
-fn count_diffs<'a>(
-    left: impl Iterator<Item = impl Iterator<Item = &'a u8>>,
-    right: impl Iterator<Item = impl Iterator<Item = &'a u8>>,
-) -> usize {
-    left.zip(right).map(|(a, b)| a.zip(b).filter(|(x, y)| x != y).count()).sum()
+// each row and each column of a pattern as bits, set where the rocks are
+#[derive(Clone, Copy, Default)]
+pub struct Pattern {
+    rows: Lines,
+    cols: Lines,
 }
 
-fn reflection_term(grid: Grid, diffs: usize) -> usize {
-    for row in 0..grid.len() - 1 {
-        let num_rows = (grid.len() - row - 1).min(row + 1);
-        let (from, to) = (row + 1 - num_rows, row + num_rows + 1);
-        let above = grid[from..row + 1].iter().map(|row| row.iter());
-        let below = grid[row + 1..to].iter().map(|row| row.iter()).rev();
-        if count_diffs(above, below) == diffs {
-            return (row + 1) * 100;
-        }
+pub type Patterns = StaticVec<Pattern, 128>;
+
+// the lines before which the ones on either side differ in `diffs` places
+fn reflection(lines: &Lines, diffs: usize) -> Option<usize> {
+    (1..lines.len()).find(|&at| {
+        let pairs = (0..at.min(lines.len() - at)).map(|i| (lines[at - 1 - i], lines[at + i]));
+        pairs.map(|(a, b)| (a ^ b).count_ones() as usize).sum::<usize>() == diffs
+    })
+}
+
+fn reflection_term(pattern: &Pattern, diffs: usize) -> usize {
+    match reflection(&pattern.rows, diffs) {
+        Some(rows) => rows * 100,
+        None => reflection(&pattern.cols, diffs).unwrap_or(0),
     }
-    for col in 0..grid[0].len() - 1 {
-        let num_cols = (grid[0].len() - col - 1).min(col + 1);
-        let (from, to) = (col + 1 - num_cols, col + num_cols + 1);
-        let left = (from..col + 1).map(|col| grid.iter().map(move |row| &row[col]));
-        let right = (col + 1..to).map(|col| grid.iter().map(move |row| &row[col]));
-        if count_diffs(left, right.rev()) == diffs {
-            return col + 1;
+}
+
+fn parse_pattern(pattern: &str) -> Pattern {
+    let width = pattern.lines().next().unwrap().len();
+    let mut parsed = Pattern { rows: Lines::empty(), cols: (0..width).map(|_| 0).collect() };
+    for (i, line) in pattern.lines().enumerate() {
+        let mut row = 0;
+        for (j, b) in line.bytes().enumerate() {
+            let rock = (b == b'#') as u32;
+            row |= rock << j;
+            parsed.cols[j] |= rock << i;
         }
+        parsed.rows.push(row);
     }
-    0
+    parsed
 }
 
-fn parse(pattern: &str) -> Grid {
-    pattern.lines().map(|line| line.bytes().collect()).collect()
+pub fn parse(input: &str) -> Patterns {
+    input.split("\n\n").map(parse_pattern).collect()
 }
 
-fn summary(input: &str, diffs: usize) -> usize {
-    input.split("\n\n").map(parse).map(|p| reflection_term(p, diffs)).sum()
+fn summary(patterns: &Patterns, diffs: usize) -> usize {
+    patterns.iter().map(|p| reflection_term(p, diffs)).sum()
 }
 
-pub fn part1(input: &str) -> usize {
-    summary(input, 0)
+pub fn part1(patterns: &Patterns) -> usize {
+    summary(patterns, 0)
 }
 
-pub fn part2(input: &str) -> usize {
-    summary(input, 1)
+pub fn part2(patterns: &Patterns) -> usize {
+    summary(patterns, 1)
 }
 
 #[cfg(test)]
@@ -69,14 +79,8 @@ mod test {
 ..##..###
 #....#..#
 ";
-        assert_eq!(part1(input), 405);
-        assert_eq!(part2(input), 400);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day13.txt");
-        assert_eq!(part1(input), 27502);
-        assert_eq!(part2(input), 31947);
+        let patterns = parse(input);
+        assert_eq!(part1(&patterns), 405);
+        assert_eq!(part2(&patterns), 400);
     }
 }
@@ -1,163 +1,242 @@
-use crate::{static_treemap::StaticTreeMap, static_vec::StaticVec};
+use crate::params::{self, Error};
+use crate::viz::Observer;
 
-type Grid = StaticVec<StaticVec<Tile, 128>, 128>;
+const MAX: usize = 128;
+
+// one bit per column of each row, or per row of each column once transposed
+type Lines = [u128; MAX];
 
 #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
-enum Tile {
+pub enum Tile {
     #[default]
     Empty,
     Round,
     Cube,
 }
 
-fn parse(input: &str) -> Grid {
-    let tile = |value| match value {
-        b'.' => Tile::Empty,
-        b'#' => Tile::Cube,
-        b'O' => Tile::Round,
-        _ => panic!("invalid tile"),
-    };
-    input.lines().map(|line| line.bytes().map(tile).collect()).collect()
-}
-
-fn roll_north(grid: &mut Grid) {
-    for col in 0..grid[0].len() {
-        let mut avail = 0;
-        for row in 0..grid.len() {
-            match grid[row][col] {
-                Tile::Round if avail == row => avail = row + 1,
-                Tile::Cube => avail = row + 1,
-                Tile::Round => {
-                    (grid[avail][col], grid[row][col]) = (Tile::Round, Tile::Empty);
-                    avail += 1;
-                }
-                Tile::Empty => {}
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Grid {
+    height: usize,
+    width: usize,
+    round: Lines,
+    cube: Lines,
+    // the cubes never move, so they are kept transposed as well
+    cube_cols: Lines,
+}
+
+pub fn parse(input: &str) -> Grid {
+    let width = input.lines().next().unwrap().len();
+    let height = input.lines().count();
+    let mut grid = Grid { height, width, round: [0; MAX], cube: [0; MAX], cube_cols: [0; MAX] };
+    for (row, line) in input.lines().enumerate() {
+        for (col, value) in line.bytes().enumerate() {
+            match value {
+                b'.' => {}
+                b'#' => grid.cube[row] |= 1 << col,
+                b'O' => grid.round[row] |= 1 << col,
+                _ => panic!("invalid tile"),
             }
         }
     }
+    grid.cube_cols = transpose(&grid.cube);
+    grid
 }
 
-fn roll_south(grid: &mut Grid) {
-    for col in 0..grid[0].len() {
-        let mut avail = grid.len();
-        for row in (0..grid.len()).rev() {
-            match grid[row][col] {
-                Tile::Round if avail - 1 == row => avail = row,
-                Tile::Cube => avail = row,
-                Tile::Round => {
-                    (grid[avail - 1][col], grid[row][col]) = (Tile::Round, Tile::Empty);
-                    avail -= 1;
-                }
-                Tile::Empty => {}
-            }
+// swaps ever smaller blocks across the diagonal, as in hacker's delight
+fn transpose(lines: &Lines) -> Lines {
+    let mut out = *lines;
+    let (mut j, mut mask) = (64, u64::MAX as u128);
+    while j != 0 {
+        for k in (0..MAX).filter(|k| k & j == 0) {
+            let t = ((out[k] >> j) ^ out[k + j]) & mask;
+            out[k] ^= t << j;
+            out[k + j] ^= t;
         }
+        j >>= 1;
+        mask ^= mask << j;
     }
+    out
 }
 
-fn roll_west(grid: &mut Grid) {
-    for row in 0..grid.len() {
-        let mut avail = 0;
-        for col in 0..grid[0].len() {
-            match grid[row][col] {
-                Tile::Round if avail == col => avail = col + 1,
-                Tile::Cube => avail = col + 1,
-                Tile::Round => {
-                    (grid[row][avail], grid[row][col]) = (Tile::Round, Tile::Empty);
-                    avail += 1;
-                }
-                Tile::Empty => {}
-            }
+// a count per column, bit sliced so that slice i holds bit i of every count,
+// with enough slices for a stretch the whole length of the grid
+const SLICES: usize = 8;
+type Counts = [u128; SLICES];
+
+fn increment(counts: &mut Counts, mut carry: u128) {
+    for slice in counts {
+        if carry == 0 {
+            break;
+        }
+        (*slice, carry) = (*slice ^ carry, *slice & carry);
+    }
+}
+
+fn decrement(counts: &mut Counts, mut borrow: u128) {
+    for slice in counts {
+        if borrow == 0 {
+            break;
+        }
+        (*slice, borrow) = (*slice ^ borrow, !*slice & borrow);
+    }
+}
+
+// counts the rocks in each stretch between cubes working back from the far
+// end, then piles them up against the start of their stretch, for every line
+// in the direction they roll at once
+fn slide(round: &mut Lines, cube: &Lines, len: usize, toward_first: bool) {
+    let line = |n: usize| if toward_first { n } else { len - 1 - n };
+    let mut stretches = [[0; SLICES]; MAX];
+    let mut counts = [0; SLICES];
+    for n in (0..len).rev() {
+        increment(&mut counts, round[line(n)]);
+        let starts = if n == 0 { !0 } else { cube[line(n - 1)] };
+        for (stretch, count) in stretches[n].iter_mut().zip(&mut counts) {
+            *stretch = *count & starts;
+            *count &= !starts;
         }
     }
+    for (n, stretch) in stretches[..len].iter().enumerate() {
+        counts.iter_mut().zip(stretch).for_each(|(count, rocks)| *count |= rocks);
+        let rocks = counts.iter().fold(0, |rocks, count| rocks | count);
+        decrement(&mut counts, rocks);
+        round[line(n)] = rocks;
+    }
 }
 
-fn roll_east(grid: &mut Grid) {
-    for row in 0..grid.len() {
-        let mut avail = grid[0].len();
-        for col in (0..grid[0].len()).rev() {
-            match grid[row][col] {
-                Tile::Round if avail - 1 == col => avail = col,
-                Tile::Cube => avail = col,
-                Tile::Round => {
-                    (grid[row][avail - 1], grid[row][col]) = (Tile::Round, Tile::Empty);
-                    avail -= 1;
-                }
-                Tile::Empty => {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Dir {
+    North,
+    West,
+    South,
+    East,
+}
+
+fn roll(grid: &mut Grid, dir: Dir, obs: &mut impl Observer<Tile>) {
+    let mut round = grid.round;
+    match dir {
+        Dir::North => slide(&mut round, &grid.cube, grid.height, true),
+        Dir::South => slide(&mut round, &grid.cube, grid.height, false),
+        Dir::West | Dir::East => {
+            let mut cols = transpose(&round);
+            slide(&mut cols, &grid.cube_cols, grid.width, dir == Dir::West);
+            round = transpose(&cols);
+        }
+    }
+    for (row, (old, new)) in grid.round.iter().zip(&round).enumerate() {
+        for (mut bits, tile) in [(old & !new, Tile::Empty), (new & !old, Tile::Round)] {
+            while bits != 0 {
+                obs.cell_changed((row, bits.trailing_zeros() as usize), tile);
+                bits &= bits - 1;
             }
         }
     }
+    grid.round = round;
 }
 
-fn cycle(grid: &mut Grid) {
-    roll_north(grid);
-    roll_west(grid);
-    roll_south(grid);
-    roll_east(grid);
+fn cycle(grid: &mut Grid, obs: &mut impl Observer<Tile>) {
+    for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+        roll(grid, dir, obs);
+        obs.frame();
+    }
 }
 
 fn total_load(grid: &Grid) -> usize {
-    grid.iter()
-        .map(|row| row.iter().filter(|t| **t == Tile::Round).count())
+    grid.round[..grid.height]
+        .iter()
         .enumerate()
-        .map(|(i, n)| n * (grid.len() - i))
+        .map(|(i, row)| row.count_ones() as usize * (grid.height - i))
         .sum()
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut grid = parse(input);
-    roll_north(&mut grid);
+pub fn part1(grid: &Grid) -> usize {
+    let mut grid = *grid;
+    roll(&mut grid, Dir::North, &mut ());
     total_load(&grid)
 }
 
-// the cache is too big for the stack :(
-static EMPTY_GRID: Grid = Grid::of(StaticVec::of(Tile::Empty));
-static mut CACHE: StaticTreeMap<Grid, usize, 1024> = StaticTreeMap::empty_of(EMPTY_GRID, 0);
-fn cache_clear() {
-    unsafe {
-        CACHE.clear();
-    }
+// how many spin cycles part 2 runs
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub cycles: usize,
 }
-fn cache_get(grid: &Grid) -> Option<usize> {
-    unsafe { CACHE.get(grid).copied() }
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { cycles: 1000000000 }
+    }
 }
-fn cache_set(grid: &Grid, i: usize) {
-    unsafe {
-        CACHE.insert(*grid, i);
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["cycles"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "cycles" => self.cycles = params::parse(value)?,
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
     }
 }
 
-pub fn part2(input: &str) -> usize {
-    cache_clear();
-    let mut grid = parse(input);
-    let iterations = 1000000000;
+fn spin(grid: &Grid, iterations: usize, obs: &mut impl Observer<Tile>) -> usize {
+    let mut grid = *grid;
+    for row in 0..grid.height {
+        for col in 0..grid.width {
+            let tile = match (grid.round[row] >> col & 1, grid.cube[row] >> col & 1) {
+                (1, _) => Tile::Round,
+                (_, 1) => Tile::Cube,
+                _ => Tile::Empty,
+            };
+            obs.cell_changed((row, col), tile);
+        }
+    }
+    obs.frame();
 
-    // find the cycle length
-    let (mut first, mut second) = (0, 0);
-    for i in 0..iterations {
-        if let Some(j) = cache_get(&grid) {
-            (first, second) = (j, i);
+    // find the cycle length with brent's algorithm, which compares whole
+    // grids and keeps only one besides: the tortoise jumps to the hare at
+    // every power of two until the hare comes back to it. the hare is then in
+    // the cycle, so the repeats can be skipped and what's left iterated
+    let mut tortoise = grid;
+    let (mut i, mut len, mut power) = (0, 0, 1);
+    let mut remaining = 0;
+    while i < iterations {
+        cycle(&mut grid, obs);
+        if obs.stopped() {
+            return total_load(&grid);
+        }
+        (i, len) = (i + 1, len + 1);
+        if grid == tortoise {
+            remaining = (iterations - i) % len;
             break;
-        } else {
-            cache_set(&grid, i);
         }
-        cycle(&mut grid);
+        if len == power {
+            (tortoise, len, power) = (grid, 0, power * 2);
+        }
     }
-
-    // apply the cycles and then iterate until done
-    let repeats = (iterations - first) / (second - first);
-    let remaining = iterations % repeats - first;
     for _ in 0..remaining {
-        cycle(&mut grid);
+        cycle(&mut grid, obs);
     }
 
     total_load(&grid)
 }
 
+pub fn part2_observed(grid: &Grid, obs: &mut impl Observer<Tile>) -> usize {
+    spin(grid, Params::default().cycles, obs)
+}
+
+pub fn part2_with(grid: &Grid, params: &Params) -> usize {
+    spin(grid, params.cycles, &mut ())
+}
+
+pub fn part2(grid: &Grid) -> usize {
+    part2_with(grid, &Params::default())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
-    // the tests have to run sequentially because we're using globals
     #[test]
     fn test() {
         let input = "O....#....
@@ -171,8 +250,8 @@ O.#..O.#.#
 #....###..
 #OO..#....
 ";
-        assert_eq!(part1(input), 136);
-        assert_eq!(part2(input), 64);
+        assert_eq!(part1(&parse(input)), 136);
+        assert_eq!(part2(&parse(input)), 64);
 
         let input = "O....#....
 O.OO#....#
@@ -185,8 +264,9 @@ O.#..O.#.#
 #....###..
 #OO..#....
 ";
-        let mut grid = parse(input);
-        cycle(&mut grid);
+        let start = parse(input);
+        let mut grid = start;
+        cycle(&mut grid, &mut ());
         assert_eq!(
             parse(
                 ".....#....
@@ -203,7 +283,7 @@ O.#..O.#.#
             ),
             grid
         );
-        cycle(&mut grid);
+        cycle(&mut grid, &mut ());
         assert_eq!(
             parse(
                 ".....#....
@@ -220,7 +300,7 @@ O.#..O.#.#
             ),
             grid
         );
-        cycle(&mut grid);
+        cycle(&mut grid, &mut ());
         assert_eq!(
             parse(
                 ".....#....
@@ -237,9 +317,22 @@ O.#..O.#.#
             ),
             grid
         );
+        assert_eq!(part2_with(&start, &Params { cycles: 3 }), total_load(&grid));
+        for _ in 3..20 {
+            cycle(&mut grid, &mut ());
+        }
+        assert_eq!(part2_with(&start, &Params { cycles: 20 }), total_load(&grid));
+    }
 
-        let input = include_str!("../inputs/day14.txt");
-        assert_eq!(part1(input), 109638);
-        assert_eq!(part2(input), 102657);
+    #[test]
+    fn test_full_stretch() {
+        // as many rocks in a column as there can be
+        let mut input: heapless::String<{ 3 * MAX }> = heapless::String::new();
+        for _ in 0..MAX {
+            input.push_str("O.\n").unwrap();
+        }
+        let grid = parse(&input);
+        assert_eq!(part1(&grid), MAX * (MAX + 1) / 2);
+        assert_eq!(part2(&grid), MAX * (MAX + 1) / 2);
     }
 }
@@ -1,21 +1,18 @@
 use crate::static_vec::StaticVec;
+use heapless::Vec;
 
 fn hash(s: &str) -> u8 {
     s.bytes().fold(0u16, |cur, b| (cur + b as u16) * 17 % 256) as u8
 }
 
-pub fn part1(input: &str) -> i64 {
-    input.split(',').map(str::trim).map(hash).map(|h| h as i64).sum()
-}
-
 #[derive(Debug)]
-enum Op<'a> {
+pub enum Op<'a> {
     Insert(&'a str, u8),
     Remove(&'a str),
 }
 
-fn parse_op(s: &str) -> Op {
-    let i = s.find(|c| c == '-' || c == '=').unwrap();
+fn parse_op(s: &str) -> Op<'_> {
+    let i = s.find(['-', '=']).unwrap();
     let (lens, tail) = (&s[..i], &s[i..]);
     if tail.starts_with('-') {
         Op::Remove(lens)
@@ -24,6 +21,18 @@ fn parse_op(s: &str) -> Op {
     }
 }
 
+// each step as written, which part 1 hashes, and as the operation part 2 does
+pub type Steps<'a> = Vec<(&'a str, Op<'a>), 4096>;
+
+pub fn parse(input: &str) -> Steps<'_> {
+    let steps = input.split(',').map(str::trim).map(|step| (step, parse_op(step)));
+    steps.collect()
+}
+
+pub fn part1(steps: &Steps) -> i64 {
+    steps.iter().map(|(step, _)| hash(step) as i64).sum()
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 struct Lens<'a> {
     label: &'a str,
@@ -74,20 +83,19 @@ fn insert<'a>(mem: &mut Memory<'a>, ptr: &mut Ptr, label: &'a str, value: u8) {
     }
 }
 
-fn apply<'a>(mem: &mut Memory<'a>, boxes: &mut Boxes, op: Op<'a>) {
-    match op {
+fn apply<'a>(mem: &mut Memory<'a>, boxes: &mut Boxes, op: &Op<'a>) {
+    match *op {
         Op::Insert(label, value) => insert(mem, &mut boxes[hash(label) as usize], label, value),
         Op::Remove(label) => remove(mem, &mut boxes[hash(label) as usize], label),
     }
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(steps: &Steps) -> usize {
     let mut mem = Memory::empty();
     let mut boxes = Boxes::of(None);
 
     // apply all operations
-    for tok in input.split(',').map(str::trim) {
-        let op = parse_op(tok);
+    for (_, op) in steps {
         apply(&mut mem, &mut boxes, op);
     }
 
@@ -113,14 +121,8 @@ mod test {
     #[test]
     fn test_examples() {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!(part1(input), 1320);
-        assert_eq!(part2(input), 145);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day15.txt");
-        assert_eq!(part1(input), 508498);
-        assert_eq!(part2(input), 279116);
+        let steps = parse(input);
+        assert_eq!(part1(&steps), 1320);
+        assert_eq!(part2(&steps), 145);
     }
 }
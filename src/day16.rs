@@ -1,13 +1,14 @@
+use crate::viz::Observer;
 use crate::{static_queue::StaticQueue, static_vec::StaticVec};
-use heapless::FnvIndexSet;
+use heapless::{FnvIndexSet, Vec};
 
-type Grid = StaticVec<StaticVec<Tile, 128>, 128>;
+pub type Grid = StaticVec<StaticVec<Tile, 128>, 128>;
 type Queue<T> = StaticQueue<T, 32768>;
 type Set<T> = FnvIndexSet<T, 32768>;
 type Pt = (i8, i8);
 
 #[derive(Debug, Clone, Copy, Default)]
-enum Tile {
+pub enum Tile {
     #[default]
     Empty,
     MirrorUp,
@@ -16,7 +17,13 @@ enum Tile {
     SplitLeftRight,
 }
 
-fn parse(input: &str) -> Grid {
+#[derive(Debug, Clone, Copy)]
+pub enum Cell {
+    Tile(Tile),
+    Energized(Tile),
+}
+
+pub fn parse(input: &str) -> Grid {
     let tile = |b| match b {
         b'.' => Tile::Empty,
         b'/' => Tile::MirrorUp,
@@ -85,55 +92,257 @@ fn in_grid(grid: &Grid, (r, c): Pt) -> bool {
     r >= 0 && r < grid.len() as i8 && c >= 0 && c < grid[r as usize].len() as i8
 }
 
-fn explore(grid: &Grid, energized: &mut Set<Pt>, start @ (pt, _dir): (Pt, Dir)) {
+fn energize(grid: &Grid, energized: &mut Set<Pt>, pt @ (r, c): Pt, obs: &mut impl Observer<Cell>) {
+    energized.insert(pt).unwrap();
+    obs.cell_changed((r as usize, c as usize), Cell::Energized(grid[r as usize][c as usize]));
+}
+
+fn explore(
+    grid: &Grid,
+    energized: &mut Set<Pt>,
+    start @ (pt, _dir): (Pt, Dir),
+    obs: &mut impl Observer<Cell>,
+) {
     let mut q = Queue::new();
     let mut v: Set<(Pt, Dir)> = Set::new();
     q.push_back(start);
     v.insert(start).unwrap();
-    energized.insert(pt).unwrap();
+    energize(grid, energized, pt, obs);
     while let Some((pt, dir)) = q.pop_front() {
         for next @ (nbr, _) in advance(grid, pt, dir) {
             if v.contains(&next) || !in_grid(grid, nbr) {
                 continue;
             }
-            energized.insert(nbr).unwrap();
+            energize(grid, energized, nbr, obs);
             v.insert(next).unwrap();
             q.push_back(next);
         }
+        obs.frame();
+        if obs.stopped() {
+            return;
+        }
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let grid = parse(input);
+pub fn part1_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> usize {
+    for (row, tiles) in grid.iter().enumerate() {
+        for (col, tile) in tiles.iter().enumerate() {
+            obs.cell_changed((row, col), Cell::Tile(*tile));
+        }
+    }
+    obs.frame();
     let mut energized = Set::new();
-    explore(&grid, &mut energized, ((0, 0), Dir::Right));
+    explore(grid, &mut energized, ((0, 0), Dir::Right), obs);
     energized.len()
 }
 
-pub fn part2(input: &str) -> usize {
-    let grid = parse(input);
-    let mut max = 0;
-    let mut energized = Set::new();
-    for (i, dir) in [(0, Dir::Down), (grid.len() as i8 - 1, Dir::Up)] {
-        for j in 0..grid[0].len() {
-            energized.clear();
-            explore(&grid, &mut energized, ((i, j as i8), dir));
-            max = max.max(energized.len());
+pub fn part1(grid: &Grid) -> usize {
+    part1_observed(grid, &mut ())
+}
+
+const MAX_CELLS: usize = 128 * 128;
+const MAX_SPLITTERS: usize = 2048;
+const WORDS: usize = MAX_CELLS / 64;
+const NONE: u16 = u16::MAX;
+
+type Bits = [u64; WORDS];
+
+// follows a beam until it leaves the grid or hits a splitter side on, which is
+// returned. a beam that does neither must be going around in a loop.
+fn trace(grid: &Grid, (mut pt, mut dir): (Pt, Dir), mut visit: impl FnMut(Pt)) -> Option<Pt> {
+    for _ in 0..4 * MAX_CELLS {
+        if !in_grid(grid, pt) {
+            return None;
+        }
+        visit(pt);
+        let next = advance(grid, pt, dir);
+        if next.len() == 2 {
+            return Some(pt);
         }
+        (pt, dir) = next[0];
+    }
+    None
+}
+
+// the two beams out of a splitter, which are the same whichever side it was
+// hit on
+fn split(grid: &Grid, pt: Pt) -> StaticVec<(Pt, Dir), 2> {
+    match advance(grid, pt, Dir::Right) {
+        beams if beams.len() == 2 => beams,
+        _ => advance(grid, pt, Dir::Down),
     }
-    for i in 0..grid.len() {
-        for (j, dir) in [(0, Dir::Right), (grid[0].len() as i8 - 1, Dir::Left)] {
-            energized.clear();
-            explore(&grid, &mut energized, ((i as i8, j), dir));
-            max = max.max(energized.len());
+}
+
+// the beams between splitters, collapsed into strongly connected components,
+// each with the cells energized by any beam that reaches it. the components
+// are found on demand with tarjan's algorithm, which finishes a component only
+// after everything it leads to, so its cells can be unioned right away. the
+// cells are kept for as many components as there's room for, and traced again
+// for the rest whenever they're needed.
+struct Beams<'a> {
+    grid: &'a Grid,
+    width: usize,
+    words: usize,
+    ids: [u16; MAX_CELLS],
+    pts: StaticVec<Pt, MAX_SPLITTERS>,
+    index: [u16; MAX_SPLITTERS],
+    low: [u16; MAX_SPLITTERS],
+    comp: [u16; MAX_SPLITTERS],
+    stack: StaticVec<u16, MAX_SPLITTERS>,
+    on_stack: [bool; MAX_SPLITTERS],
+    next_index: u16,
+    // the splitters of each component, which are order[starts[c]..starts[c + 1]]
+    comps: usize,
+    order: StaticVec<u16, MAX_SPLITTERS>,
+    starts: [u16; MAX_SPLITTERS + 1],
+    // where in `reach` each component's energized cells are kept, `words`
+    // apiece, or NONE
+    slots: [u16; MAX_SPLITTERS],
+    reach: Vec<u64, 65536>,
+}
+
+impl<'a> Beams<'a> {
+    fn new(grid: &'a Grid) -> Self {
+        let width = grid[0].len();
+        let mut beams = Beams {
+            grid,
+            width,
+            words: (grid.len() * width).div_ceil(64),
+            ids: [NONE; MAX_CELLS],
+            pts: StaticVec::empty(),
+            index: [NONE; MAX_SPLITTERS],
+            low: [NONE; MAX_SPLITTERS],
+            comp: [NONE; MAX_SPLITTERS],
+            stack: StaticVec::empty(),
+            on_stack: [false; MAX_SPLITTERS],
+            next_index: 0,
+            comps: 0,
+            order: StaticVec::empty(),
+            starts: [0; MAX_SPLITTERS + 1],
+            slots: [NONE; MAX_SPLITTERS],
+            reach: Vec::new(),
+        };
+        for (r, tiles) in grid.iter().enumerate() {
+            for (c, tile) in tiles.iter().enumerate() {
+                if matches!(tile, Tile::SplitUpDown | Tile::SplitLeftRight) {
+                    beams.ids[r * width + c] = beams.pts.len() as u16;
+                    beams.pts.push((r as i8, c as i8));
+                }
+            }
         }
+        beams
+    }
+
+    fn id(&self, (r, c): Pt) -> usize {
+        self.ids[r as usize * self.width + c as usize] as usize
     }
-    max
+
+    fn connect(&mut self, v: usize) {
+        self.index[v] = self.next_index;
+        self.low[v] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(v as u16);
+        self.on_stack[v] = true;
+        for beam in split(self.grid, self.pts[v]) {
+            let Some(w) = trace(self.grid, beam, |_| ()).map(|pt| self.id(pt)) else {
+                continue;
+            };
+            if self.index[w] == NONE {
+                self.connect(w);
+                self.low[v] = self.low[v].min(self.low[w]);
+            } else if self.on_stack[w] {
+                self.low[v] = self.low[v].min(self.index[w]);
+            }
+        }
+        if self.low[v] == self.index[v] {
+            self.finish(v);
+        }
+    }
+
+    fn finish(&mut self, root: usize) {
+        let comp = self.comps;
+        self.comps += 1;
+        let start = self.order.len();
+        while self.order.len() == start || self.order[self.order.len() - 1] as usize != root {
+            let w = self.stack.pop();
+            self.on_stack[w as usize] = false;
+            self.comp[w as usize] = comp as u16;
+            self.order.push(w);
+        }
+        self.starts[comp + 1] = self.order.len() as u16;
+        let mut bits = [0; WORDS];
+        self.gather(&mut bits, comp);
+        if self.reach.len() + self.words <= self.reach.capacity() {
+            self.slots[comp] = (self.reach.len() / self.words) as u16;
+            self.reach.extend_from_slice(&bits[..self.words]).unwrap();
+        }
+    }
+
+    // marks the cells energized from a component: the kept ones if it has
+    // them, or else its own beams', and then those of the components they lead
+    // to, which are all finished, in the same way
+    fn gather(&self, bits: &mut Bits, comp: usize) {
+        let mut seen = [false; MAX_SPLITTERS];
+        let mut todo: StaticVec<u16, MAX_SPLITTERS> = StaticVec::empty();
+        seen[comp] = true;
+        todo.push(comp as u16);
+        while !todo.is_empty() {
+            let c = todo.pop() as usize;
+            if self.slots[c] != NONE {
+                let from = self.slots[c] as usize * self.words;
+                for (word, other) in bits.iter_mut().zip(&self.reach[from..from + self.words]) {
+                    *word |= other;
+                }
+                continue;
+            }
+            for &w in &self.order[self.starts[c] as usize..self.starts[c + 1] as usize] {
+                let pt = self.pts[w as usize];
+                self.mark(bits, pt);
+                for beam in split(self.grid, pt) {
+                    let hit = trace(self.grid, beam, |pt| self.mark(bits, pt));
+                    if let Some(next) = hit.map(|pt| self.comp[self.id(pt)] as usize) {
+                        if !seen[next] {
+                            seen[next] = true;
+                            todo.push(next as u16);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn mark(&self, bits: &mut Bits, (r, c): Pt) {
+        let i = r as usize * self.width + c as usize;
+        bits[i / 64] |= 1 << (i % 64);
+    }
+
+    fn energized(&mut self, start: (Pt, Dir)) -> usize {
+        let mut bits = [0; WORDS];
+        let hit = trace(self.grid, start, |pt| self.mark(&mut bits, pt));
+        if let Some(v) = hit.map(|pt| self.id(pt)) {
+            if self.index[v] == NONE {
+                self.connect(v);
+            }
+            self.gather(&mut bits, self.comp[v] as usize);
+        }
+        bits[..self.words].iter().map(|word| word.count_ones() as usize).sum()
+    }
+}
+
+pub fn part2(grid: &Grid) -> usize {
+    let (height, width) = (grid.len() as i8, grid[0].len() as i8);
+    let mut beams = Beams::new(grid);
+    let down = (0..width).map(|c| ((0, c), Dir::Down));
+    let up = (0..width).map(|c| ((height - 1, c), Dir::Up));
+    let right = (0..height).map(|r| ((r, 0), Dir::Right));
+    let left = (0..height).map(|r| ((r, width - 1), Dir::Left));
+    down.chain(up).chain(right).chain(left).map(|start| beams.energized(start)).max().unwrap()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen;
 
     #[test]
     fn test_examples() {
@@ -148,14 +357,16 @@ mod test {
 .|....-|.\
 ..//.|....
 ";
-        assert_eq!(part1(input), 46);
-        assert_eq!(part2(input), 51);
+        let grid = parse(input);
+        assert_eq!(part1(&grid), 46);
+        assert_eq!(part2(&grid), 51);
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day16.txt");
-        assert_eq!(part1(input), 7798);
-        assert_eq!(part2(input), 8026);
+    fn test_many_components() {
+        // more components than there's room to keep the cells of
+        let grid = parse(&gen::input(16, 33, 110));
+        assert_eq!(part1(&grid), 98);
+        assert_eq!(part2(&grid), 6436);
     }
 }
@@ -1,145 +1,211 @@
-use heapless::{
-    binary_heap::{BinaryHeap, Min},
-    Vec,
-};
+use crate::viz::{Observer, Pos};
+use heapless::Vec;
 
 type Pt = (u8, u8);
-type Grid = Vec<Vec<u8, 256>, 256>;
-type MinQueue<T> = BinaryHeap<T, Min, 16384>;
-type Costs = Vec<Vec<[u64; 4], 256>, 256>;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
+pub type Grid = Vec<Vec<u8, 256>, 256>;
+
+const MAX_STATES: usize = 256 * 256 * 2;
+// more than the most a single move can add to a priority: ten blocks of nine
+// plus the ten the heuristic can grow by, so the ring never laps itself
+const BUCKETS: usize = 128;
+const NIL: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Block(u8),
+    Frontier,
+    Settled,
 }
 
-impl Dir {
-    fn apply(self, (row, col): Pt, grid: &Grid) -> Option<Pt> {
-        match self {
-            Dir::Up if row > 0 => Some((row - 1, col)),
-            Dir::Down if row < grid.len() as u8 - 1 => Some((row + 1, col)),
-            Dir::Left if col > 0 => Some((row, col - 1)),
-            Dir::Right if col < grid[0].len() as u8 - 1 => Some((row, col + 1)),
-            _ => None,
-        }
-    }
+fn pos((row, col): Pt) -> Pos {
+    (row as usize, col as usize)
+}
 
-    fn turns(self) -> [Dir; 2] {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Heuristic {
+    Dijkstra,
+    Manhattan,
+}
+
+impl Heuristic {
+    fn estimate(self, (row, col): Pt, (end_row, end_col): Pt) -> usize {
         match self {
-            Dir::Right => [Dir::Up, Dir::Down],
-            Dir::Up => [Dir::Left, Dir::Right],
-            Dir::Left => [Dir::Up, Dir::Down],
-            Dir::Down => [Dir::Left, Dir::Right],
+            Heuristic::Dijkstra => 0,
+            // every block costs at least one
+            Heuristic::Manhattan => (end_row - row) as usize + (end_col - col) as usize,
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct Step {
+// a block, and whether the crucible got there moving vertically or
+// horizontally, since either way the next move turns. the direction it was
+// moving in doesn't matter: the next move can go either way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct State {
     pt: Pt,
-    dir: Dir,
+    vertical: bool,
 }
 
-impl Step {
-    fn advance(self, grid: &Grid) -> Option<(u64, Step)> {
-        self.dir.apply(self.pt, grid).map(|pt @ (row, col)| {
-            (grid[row as usize][col as usize] as u64, Step { pt, dir: self.dir })
-        })
+impl State {
+    fn index(self, width: usize) -> usize {
+        (self.pt.0 as usize * width + self.pt.1 as usize) * 2 + self.vertical as usize
     }
-}
 
-fn neighbors(grid: &Grid, mut cur: Step, min: u8, max: u8) -> Vec<(u64, Step), 16> {
-    let mut nbrs = Vec::new();
-    let mut cost = 0;
-    for _ in 0..min {
-        if let Some((step_cost, step)) = cur.advance(grid) {
-            cost += step_cost;
-            cur = step;
-        } else {
-            return Vec::new();
-        };
+    fn of(index: usize, width: usize) -> State {
+        let (block, vertical) = (index / 2, index % 2 == 1);
+        State { pt: ((block / width) as u8, (block % width) as u8), vertical }
     }
-    for _ in 0..max - min + 1 {
-        for dir in cur.dir.turns() {
-            nbrs.push((cost, Step { pt: cur.pt, dir })).unwrap();
+}
+
+// dial's algorithm: a ring of buckets by priority, each a circular doubly
+// linked list through the states, which come first in `links`, and the
+// buckets' own sentinels after them. a state is queued at most once, moving
+// to a cheaper bucket when a cheaper way to it is found, so the queue never
+// outgrows the states
+struct BucketQueue<'a> {
+    // (prev, next), or NIL for the states that aren't queued. borrowed, as
+    // it's too big to move around
+    links: &'a mut Links,
+    states: usize,
+    cur: usize,
+    len: usize,
+}
+
+type Links = Vec<(u32, u32), { MAX_STATES + BUCKETS }>;
+
+impl<'a> BucketQueue<'a> {
+    // nothing can be pushed below `start`
+    fn new(links: &'a mut Links, states: usize, start: usize) -> Self {
+        links.clear();
+        links.resize(states, (NIL, NIL)).unwrap();
+        for bucket in states..states + BUCKETS {
+            links.push((bucket as u32, bucket as u32)).unwrap();
         }
-        if let Some((step_cost, step)) = cur.advance(grid) {
-            cost += step_cost;
-            cur = step;
-        } else {
-            break;
-        };
+        BucketQueue { links, states, cur: start, len: 0 }
     }
-    nbrs
-}
 
-// too big for the stack :(
-static mut COSTS: Costs = Costs::new();
-fn init_costs() {
-    unsafe {
-        COSTS.clear();
-        for i in 0..COSTS.capacity() {
-            COSTS.push(Vec::new()).unwrap();
-            for j in 0..COSTS[i].capacity() {
-                COSTS[i].push([0; 4]).unwrap();
-                for k in 0..4 {
-                    COSTS[i][j][k] = u64::MAX;
-                }
-            }
+    fn unlink(&mut self, node: usize) {
+        let (prev, next) = self.links[node];
+        self.links[prev as usize].1 = next;
+        self.links[next as usize].0 = prev;
+        self.links[node] = (NIL, NIL);
+    }
+
+    // queues `state`, or moves it if it already is
+    fn push(&mut self, priority: usize, state: usize) {
+        if self.links[state].0 == NIL {
+            self.len += 1;
+        } else {
+            self.unlink(state);
         }
+        let sentinel = self.states + priority % BUCKETS;
+        let next = self.links[sentinel].1;
+        self.links[state] = (sentinel as u32, next);
+        self.links[sentinel].1 = state as u32;
+        self.links[next as usize].0 = state as u32;
     }
-}
-fn get_cost(step: &Step) -> u64 {
-    unsafe { COSTS[step.pt.0 as usize][step.pt.1 as usize][step.dir as usize] }
-}
-fn set_cost(step: Step, cost: u64) {
-    unsafe {
-        COSTS[step.pt.0 as usize][step.pt.1 as usize][step.dir as usize] = cost;
+
+    fn pop(&mut self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+        let sentinel = |cur| self.states + cur % BUCKETS;
+        while self.links[sentinel(self.cur)].1 as usize == sentinel(self.cur) {
+            self.cur += 1;
+        }
+        let state = self.links[sentinel(self.cur)].1 as usize;
+        self.unlink(state);
+        self.len -= 1;
+        Some(state)
     }
 }
 
-fn min_path(grid: &Grid, start: Pt, end: Pt, min_steps: u8, max_steps: u8) -> Option<u64> {
-    init_costs();
-    let mut q = MinQueue::new();
-    for dir in [Dir::Right, Dir::Down] {
-        let step = Step { pt: start, dir };
-        q.push((0, step)).unwrap();
-        set_cost(step, 0);
+fn min_path(
+    grid: &Grid,
+    (min_steps, max_steps): (u8, u8),
+    heuristic: Heuristic,
+    obs: &mut impl Observer<Cell>,
+) -> Option<u16> {
+    for (row, blocks) in grid.iter().enumerate() {
+        for (col, block) in blocks.iter().enumerate() {
+            obs.cell_changed((row, col), Cell::Block(*block));
+        }
+    }
+    obs.frame();
+    let (height, width) = (grid.len(), grid[0].len());
+    let end = ((height - 1) as u8, (width - 1) as u8);
+    let mut costs: Vec<u16, MAX_STATES> = Vec::new();
+    costs.resize(height * width * 2, u16::MAX).unwrap();
+    let start = heuristic.estimate((0, 0), end);
+    let mut links = Vec::new();
+    let mut q = BucketQueue::new(&mut links, costs.len(), start);
+    for vertical in [false, true] {
+        let state = State { pt: (0, 0), vertical }.index(width);
+        costs[state] = 0;
+        q.push(start, state);
     }
-    while let Some((cost, step)) = q.pop() {
-        if step.pt == end {
+    while let Some(index) = q.pop() {
+        let state = State::of(index, width);
+        let cost = costs[index];
+        obs.cell_changed(pos(state.pt), Cell::Settled);
+        if state.pt == end {
+            obs.frame();
             return Some(cost);
         }
-        for (nbr_step_cost, nbr_step) in neighbors(grid, step, min_steps, max_steps) {
-            let nbr_cost = cost + nbr_step_cost;
-            if nbr_cost < get_cost(&nbr_step) {
-                q.push((nbr_cost, nbr_step)).unwrap();
-                set_cost(nbr_step, nbr_cost);
+        let turns: [(i16, i16); 2] =
+            if state.vertical { [(0, -1), (0, 1)] } else { [(-1, 0), (1, 0)] };
+        for (dr, dc) in turns {
+            let (mut row, mut col) = (state.pt.0 as i16, state.pt.1 as i16);
+            let mut nbr_cost = cost;
+            for steps in 1..=max_steps {
+                (row, col) = (row + dr, col + dc);
+                if row < 0 || row >= height as i16 || col < 0 || col >= width as i16 {
+                    break;
+                }
+                nbr_cost += grid[row as usize][col as usize] as u16;
+                if steps < min_steps {
+                    continue;
+                }
+                let pt = (row as u8, col as u8);
+                let nbr = State { pt, vertical: !state.vertical }.index(width);
+                if nbr_cost < costs[nbr] {
+                    costs[nbr] = nbr_cost;
+                    q.push(nbr_cost as usize + heuristic.estimate(pt, end), nbr);
+                    obs.cell_changed(pos(pt), Cell::Frontier);
+                }
             }
         }
+        obs.frame();
+        if obs.stopped() {
+            return Some(cost);
+        }
     }
     None
 }
 
-fn parse(input: &str) -> Grid {
+pub fn parse(input: &str) -> Grid {
     input.lines().map(|line| line.bytes().map(|b| b - b'0').collect()).collect()
 }
 
-pub fn part1(input: &str) -> u64 {
-    let grid = parse(input);
-    let start = (0, 0);
-    let end = (grid.len() as u8 - 1, grid[0].len() as u8 - 1);
-    min_path(&grid, start, end, 1, 3).unwrap()
+const CRUCIBLE: (u8, u8) = (1, 3);
+const ULTRA_CRUCIBLE: (u8, u8) = (4, 10);
+
+// observed with plain dijkstra, which spreads out evenly and so shows the
+// search better than the heuristic does
+pub fn part1_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> u64 {
+    min_path(grid, CRUCIBLE, Heuristic::Dijkstra, obs).unwrap() as u64
 }
 
-pub fn part2(input: &str) -> u64 {
-    let grid = parse(input);
-    let start = (0, 0);
-    let end = (grid.len() as u8 - 1, grid[0].len() as u8 - 1);
-    min_path(&grid, start, end, 4, 10).unwrap()
+pub fn part1(grid: &Grid) -> u64 {
+    min_path(grid, CRUCIBLE, Heuristic::Manhattan, &mut ()).unwrap() as u64
+}
+
+pub fn part2_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> u64 {
+    min_path(grid, ULTRA_CRUCIBLE, Heuristic::Dijkstra, obs).unwrap() as u64
+}
+
+pub fn part2(grid: &Grid) -> u64 {
+    min_path(grid, ULTRA_CRUCIBLE, Heuristic::Manhattan, &mut ()).unwrap() as u64
 }
 
 #[cfg(test)]
@@ -162,12 +228,32 @@ mod test {
 2546548887735
 4322674655533
 ";
-        assert_eq!(part1(input), 102);
-        assert_eq!(part2(input), 94);
+        let grid = parse(input);
+        assert_eq!(part1(&grid), 102);
+        assert_eq!(part2(&grid), 94);
+    }
 
-        // real
-        let input = include_str!("../inputs/day17.txt");
-        assert_eq!(part1(input), 1263);
-        assert_eq!(part2(input), 1411);
+    #[test]
+    fn test_heuristics_agree() {
+        let grid = parse(include_str!("../inputs/day17.txt"));
+        for crucible in [CRUCIBLE, ULTRA_CRUCIBLE] {
+            let dijkstra = min_path(&grid, crucible, Heuristic::Dijkstra, &mut ());
+            let astar = min_path(&grid, crucible, Heuristic::Manhattan, &mut ());
+            assert_eq!(dijkstra, astar);
+        }
+    }
+
+    #[test]
+    fn test_widest() {
+        let mut input: heapless::String<{ 1 << 17 }> = heapless::String::new();
+        let generator = crate::gen::find(17).unwrap();
+        (generator.generate)(&mut crate::gen::Rng::new(0), 256, &mut input).unwrap();
+        let grid = parse(&input);
+        for crucible in [CRUCIBLE, ULTRA_CRUCIBLE] {
+            let dijkstra = min_path(&grid, crucible, Heuristic::Dijkstra, &mut ());
+            let astar = min_path(&grid, crucible, Heuristic::Manhattan, &mut ());
+            assert!(dijkstra.is_some());
+            assert_eq!(dijkstra, astar);
+        }
     }
 }
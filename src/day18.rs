@@ -59,7 +59,7 @@ fn interior(cmds: &[Command]) -> i64 {
     area / 2
 }
 
-fn parse(input: &str) -> impl Iterator<Item = (Command, Command)> + '_ {
+fn parse_lines(input: &str) -> impl Iterator<Item = (Command, Command)> + '_ {
     input.lines().map(|line: &str| {
         // part 1
         let mut toks = line.split(' ');
@@ -89,14 +89,24 @@ fn parse(input: &str) -> impl Iterator<Item = (Command, Command)> + '_ {
     })
 }
 
-pub fn part1(input: &str) -> i64 {
-    let commands: Vec<Command> = parse(input).map(|x| x.0).collect();
-    interior(&commands)
+// the commands as part 1 reads them, and as part 2 reads them from the colors
+pub struct Plan {
+    commands: Vec<Command>,
+    colors: Vec<Command>,
 }
 
-pub fn part2(input: &str) -> i64 {
-    let commands: Vec<Command> = parse(input).map(|x| x.1).collect();
-    interior(&commands)
+pub fn parse(input: &str) -> Plan {
+    let commands = parse_lines(input).map(|x| x.0).collect();
+    let colors = parse_lines(input).map(|x| x.1).collect();
+    Plan { commands, colors }
+}
+
+pub fn part1(plan: &Plan) -> i64 {
+    interior(&plan.commands)
+}
+
+pub fn part2(plan: &Plan) -> i64 {
+    interior(&plan.colors)
 }
 
 #[cfg(test)]
@@ -120,8 +130,9 @@ U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)
 ";
-        assert_eq!(part1(input), 62);
-        assert_eq!(part2(input), 952408144115);
+        let plan = parse(input);
+        assert_eq!(part1(&plan), 62);
+        assert_eq!(part2(&plan), 952408144115);
     }
 
     #[test]
@@ -135,14 +146,14 @@ D 2 (#aaaaa1)
 L 2 (#aaaaa1)
 U 3 (#aaaaa1)
 ";
-        assert_eq!(part1(input), 24);
+        assert_eq!(part1(&parse(input)), 24);
 
         let input = "R 3 (#aaaaa1)
 D 1 (#aaaaa1)
 L 3 (#aaaaa1)
 U 1 (#aaaaa1)
 ";
-        assert_eq!(part1(input), 8);
+        assert_eq!(part1(&parse(input)), 8);
 
         let input = "R 2 (#aaaaa1)
 D 2 (#aaaaa1)
@@ -157,13 +168,6 @@ D 2 (#aaaaa1)
 L 2 (#aaaaa1)
 U 7 (#aaaaa1)
 ";
-        assert_eq!(part1(input), 48);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day18.txt");
-        assert_eq!(part1(input), 40761);
-        assert_eq!(part2(input), 106920098354636);
+        assert_eq!(part1(&parse(input)), 48);
     }
 }
@@ -37,7 +37,7 @@ fn apply_op(op: &Op, lhs: i16, rhs: i16) -> bool {
 
 fn apply_workflow<'a>(workflow: &'a Workflow, part: &Part) -> &'a str {
     for rule in &workflow.conds {
-        if apply_op(&rule.op, part[rule.var as usize], rule.arg) {
+        if apply_op(&rule.op, part[rule.var], rule.arg) {
             return rule.target;
         }
     }
@@ -54,7 +54,7 @@ fn is_valid(workflows: &Workflows, part: &Part) -> bool {
 
 fn sum_ratings(workflows: &Workflows, parts: &Parts) -> i64 {
     let sum = |part: &Part| -> i64 { part.iter().map(|x| *x as i64).sum() };
-    parts.iter().filter(|part| is_valid(&workflows, *part)).map(sum).sum()
+    parts.iter().filter(|part| is_valid(workflows, part)).map(sum).sum()
 }
 
 // =============================================================================
@@ -93,12 +93,12 @@ fn satisfy_workflow(
         _ => workflows.get(label).unwrap(),
     };
     for Rule { var, op, arg, target } in &workflow.conds {
-        let x = part[*var as usize];
+        let x = part[*var];
         if let Some(y) = satisfy_op(op, x, *arg) {
             let mut next = part;
             next[*var] = y;
             satisfy_workflow(target, next, workflows, valid);
-            part[*var as usize] = range_complement(x, y);
+            part[*var] = range_complement(x, y);
         }
     }
     satisfy_workflow(workflow.alt, part, workflows, valid);
@@ -118,7 +118,7 @@ fn total_valid(workflows: &Workflows) -> i64 {
 // =============================================================================
 // parsing
 
-fn parse_workflow(line: &str) -> (&str, Workflow) {
+fn parse_workflow(line: &str) -> (&str, Workflow<'_>) {
     let (label, rest) = line.split_once('{').unwrap();
     let mut rules = rest[..rest.len() - 1].split(',').rev();
     let alt = rules.next().unwrap();
@@ -154,29 +154,33 @@ fn parse_part(line: &str) -> Part {
         .unwrap()
 }
 
-fn parse(input: &str) -> (Workflows, Parts) {
+pub struct System<'a> {
+    workflows: Workflows<'a>,
+    parts: Parts,
+}
+
+pub fn parse(input: &str) -> System<'_> {
     let (workflows, parts) = input.split_once("\n\n").unwrap();
     let workflows = workflows.lines().map(parse_workflow).collect();
     let parts = parts.lines().map(parse_part).collect();
-    (workflows, parts)
+    System { workflows, parts }
 }
 
 // =============================================================================
 // solutions
 
-pub fn part1(input: &str) -> i64 {
-    let (workflows, parts) = parse(input);
-    sum_ratings(&workflows, &parts)
+pub fn part1(system: &System) -> i64 {
+    sum_ratings(&system.workflows, &system.parts)
 }
 
-pub fn part2(input: &str) -> i64 {
-    let workflows = parse(input).0;
-    total_valid(&workflows)
+pub fn part2(system: &System) -> i64 {
+    total_valid(&system.workflows)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen;
 
     #[test]
     fn test_example() {
@@ -198,14 +202,55 @@ hdj{m>838:A,pv}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}
 ";
-        assert_eq!(part1(input), 19114);
-        assert_eq!(part2(input), 167409079868000);
+        let system = parse(input);
+        assert_eq!(part1(&system), 19114);
+        assert_eq!(part2(&system), 167409079868000);
+    }
+
+    // every part, a box at a time: between the values that the rules compare
+    // each rating with, all parts go the same way, so one stands for the box
+    fn brute_force(workflows: &Workflows) -> i64 {
+        let mut cuts: [Vec<i16, 64>; 4] = Default::default();
+        for cuts in &mut cuts {
+            cuts.extend([1, 4001]);
+        }
+        for rule in workflows.values().flat_map(|workflow| &workflow.conds) {
+            let cut = match rule.op {
+                Op::Lt => rule.arg,
+                Op::Gt => rule.arg + 1,
+            };
+            if !cuts[rule.var].contains(&cut) {
+                cuts[rule.var].push(cut).unwrap();
+            }
+        }
+        for cuts in &mut cuts {
+            cuts.sort_unstable();
+        }
+        let mut total = 0;
+        let [xs, ms, as_, ss] = &cuts;
+        for x in xs.windows(2) {
+            for m in ms.windows(2) {
+                for a in as_.windows(2) {
+                    for s in ss.windows(2) {
+                        if is_valid(workflows, &[x[0], m[0], a[0], s[0]]) {
+                            let boxes = [x, m, a, s];
+                            total += boxes.iter().map(|w| (w[1] - w[0]) as i64).product::<i64>();
+                        }
+                    }
+                }
+            }
+        }
+        total
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day19.txt");
-        assert_eq!(part1(input), 367602);
-        assert_eq!(part2(input), 125317461667458);
+    fn test_brute_force() {
+        for seed in 0..32 {
+            for size in [1, 2, 4, 8] {
+                let input = gen::input(19, seed, size);
+                let workflows = parse(&input).workflows;
+                assert_eq!(total_valid(&workflows), brute_force(&workflows), "{}", input);
+            }
+        }
     }
 }
@@ -1,30 +1,60 @@
+use crate::params::{self, Error};
+use crate::static_vec::StaticVec;
 use core::iter::Iterator;
-use core::str::Split;
-use regex::Regex;
 
+#[derive(Clone, Copy, Default)]
 struct Outcome(i64, i64, i64);
 
-struct Outcomes<'a, 'b> {
-    pat: &'b Regex,
-    segs: Split<'a, char>,
+// Iterator over the outcomes of a game, scanning "3 blue, 4 red; 1 red" bytewise
+
+struct Outcomes<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Outcomes<'_> {
+    fn skip_while(&mut self, f: impl Fn(u8) -> bool) {
+        while self.bytes.get(self.pos).is_some_and(|&b| f(b)) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_amt(&mut self) -> i64 {
+        let mut amt = 0;
+        while let Some(&b @ b'0'..=b'9') = self.bytes.get(self.pos) {
+            amt = amt * 10 + (b - b'0') as i64;
+            self.pos += 1;
+        }
+        amt
+    }
 }
 
-impl Iterator for Outcomes<'_, '_> {
+impl Iterator for Outcomes<'_> {
     type Item = Outcome;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let seg = self.segs.next()?;
+        if self.pos >= self.bytes.len() {
+            return None;
+        }
         let mut outcome = Outcome(0, 0, 0);
-        for (_, [amt, col]) in self.pat.captures_iter(seg).map(|c| c.extract()) {
-            let amt = amt.parse().unwrap();
-            match col {
-                "red" => outcome.0 = amt,
-                "green" => outcome.1 = amt,
-                "blue" => outcome.2 = amt,
+        loop {
+            self.skip_while(|b| b == b' ' || b == b',');
+            let amt = self.parse_amt();
+            self.skip_while(|b| b == b' ');
+            match self.bytes.get(self.pos) {
+                Some(b'r') => outcome.0 = amt,
+                Some(b'g') => outcome.1 = amt,
+                Some(b'b') => outcome.2 = amt,
                 _ => panic!("invalid color"),
             }
+            self.skip_while(|b| b.is_ascii_lowercase());
+            match self.bytes.get(self.pos) {
+                Some(b',') => continue,
+                Some(b';') => self.pos += 1,
+                _ => self.pos = self.bytes.len(),
+            }
+            return Some(outcome);
         }
-        Some(outcome)
     }
 }
 
@@ -33,58 +63,85 @@ fn parse_game(s: &str) -> usize {
     game.parse().unwrap()
 }
 
-fn parse_outcome(s: &str) -> Outcome {
-    let (mut r, mut g, mut b) = (0, 0, 0);
-    for handful in s.split(',') {
-        let (amt, col) = handful.trim().split_once(' ').unwrap();
-        let amt = amt.parse().unwrap();
-        match col {
-            "red" => r = amt,
-            "green" => g = amt,
-            "blue" => b = amt,
-            _ => panic!("invalid color"),
-        }
+fn parse_line(line: &str) -> (usize, impl Iterator<Item = Outcome> + '_) {
+    let (game, outcomes) = line.split_once(':').unwrap();
+    let outcomes = Outcomes { bytes: outcomes.as_bytes(), pos: 0 };
+    (parse_game(game), outcomes)
+}
+
+fn most(outcomes: impl Iterator<Item = Outcome>) -> Outcome {
+    let mut most = Outcome(0, 0, 0);
+    for outcome in outcomes {
+        most.0 = most.0.max(outcome.0);
+        most.1 = most.1.max(outcome.1);
+        most.2 = most.2.max(outcome.2);
     }
-    Outcome(r, g, b)
+    most
 }
 
-fn parse(line: &str) -> (usize, impl Iterator<Item = Outcome> + '_) {
-    let (game, outcomes) = line.split_once(':').unwrap();
-    let outcomes = outcomes.split(';').map(parse_outcome);
-    (parse_game(game), outcomes)
+// a game's id and the most cubes of each color it showed at once, which is
+// all either part looks at
+#[derive(Clone, Copy, Default)]
+pub struct Game {
+    id: usize,
+    most: Outcome,
+}
+
+pub type Games = StaticVec<Game, 128>;
+
+pub fn parse(input: &str) -> Games {
+    let games = input.lines().map(parse_line);
+    games.map(|(id, outcomes)| Game { id, most: most(outcomes) }).collect()
 }
 
 fn can_fit(into: &Outcome, from: &Outcome) -> bool {
     from.0 <= into.0 && from.1 <= into.1 && from.2 <= into.2
 }
 
-pub fn part1(input: &str) -> usize {
-    let available = Outcome(12, 13, 14);
-    let all_games = input.lines().map(parse);
-    let possible_games = all_games.flat_map(|(id, mut outcomes)| {
-        if outcomes.all(|outcome| can_fit(&available, &outcome)) {
-            Some(id)
-        } else {
-            None
-        }
-    });
-    possible_games.sum()
+// the cubes in the bag
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub red: i64,
+    pub green: i64,
+    pub blue: i64,
 }
 
-fn power(outcomes: impl Iterator<Item = Outcome>) -> i64 {
-    let mut min = Outcome(0, 0, 0);
-    for outcome in outcomes {
-        min.0 = min.0.max(outcome.0);
-        min.1 = min.1.max(outcome.1);
-        min.2 = min.2.max(outcome.2);
+impl Default for Params {
+    fn default() -> Self {
+        Params { red: 12, green: 13, blue: 14 }
+    }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["red", "green", "blue"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "red" => self.red = params::parse(value)?,
+            "green" => self.green = params::parse(value)?,
+            "blue" => self.blue = params::parse(value)?,
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
     }
+}
+
+pub fn part1_with(games: &Games, params: &Params) -> usize {
+    let available = Outcome(params.red, params.green, params.blue);
+    let possible_games = games.iter().filter(|game| can_fit(&available, &game.most));
+    possible_games.map(|game| game.id).sum()
+}
+
+pub fn part1(games: &Games) -> usize {
+    part1_with(games, &Params::default())
+}
+
+fn power(min: &Outcome) -> i64 {
     min.0 * min.1 * min.2
 }
 
-pub fn part2(input: &str) -> i64 {
-    let all_games = input.lines().map(parse);
-    let powers = all_games.map(|(_, outcomes)| power(outcomes));
-    powers.sum()
+pub fn part2(games: &Games) -> i64 {
+    games.iter().map(|game| power(&game.most)).sum()
 }
 
 #[cfg(test)]
@@ -100,31 +157,25 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
 
     #[test]
     fn test1() {
-        assert_eq!(part1(INPUT), 8);
+        assert_eq!(part1(&parse(INPUT)), 8);
+        // game 3 has 20 red, and game 4 15 blue
+        let params = Params { red: 20, blue: 15, ..Params::default() };
+        assert_eq!(part1_with(&parse(INPUT), &params), 15);
     }
 
     #[test]
     fn test2_1() {
         let mut lines = INPUT.lines();
-        assert_eq!(power(parse(lines.next().unwrap()).1), 48);
-        assert_eq!(power(parse(lines.next().unwrap()).1), 12);
-        assert_eq!(power(parse(lines.next().unwrap()).1), 1560);
-        assert_eq!(power(parse(lines.next().unwrap()).1), 630);
-        assert_eq!(power(parse(lines.next().unwrap()).1), 36);
+        assert_eq!(power(&most(parse_line(lines.next().unwrap()).1)), 48);
+        assert_eq!(power(&most(parse_line(lines.next().unwrap()).1)), 12);
+        assert_eq!(power(&most(parse_line(lines.next().unwrap()).1)), 1560);
+        assert_eq!(power(&most(parse_line(lines.next().unwrap()).1)), 630);
+        assert_eq!(power(&most(parse_line(lines.next().unwrap()).1)), 36);
         assert_eq!(lines.next(), None);
     }
 
     #[test]
     fn test2_2() {
-        assert_eq!(part2(INPUT), 2286);
-    }
-
-    #[test]
-    fn test_solution() {
-        const INPUT: &str = include_str!("../inputs/day2.txt");
-        let actual1 = part1(INPUT);
-        assert_eq!(2204, actual1);
-        let actual2 = part2(INPUT);
-        assert_eq!(71036, actual2);
+        assert_eq!(part2(&parse(INPUT)), 2286);
     }
 }
@@ -1,7 +1,12 @@
-use heapless::{Deque, FnvIndexMap, Vec};
+use crate::params::{self, Error};
+use heapless::{Deque, Vec};
 
-type Connections<'a> = FnvIndexMap<&'a str, Vec<&'a str, 8>, 64>;
-type System<'a> = FnvIndexMap<&'a str, Machine<'a>, 64>;
+const MAX_MODULES: usize = 64;
+const MAX_OUTPUTS: usize = 8;
+
+type Id = u8;
+// one bit per module id
+type Mask = u64;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Pulse {
@@ -18,62 +23,152 @@ impl From<bool> for Pulse {
     }
 }
 
-#[derive(Debug, Clone)]
-enum Machine<'a> {
-    FlipFlop { on: bool },
-    Conjunction { recent: FnvIndexMap<&'a str, Pulse, 16> },
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    // only ever receives, like `rx`
+    Output,
 }
 
-#[derive(Debug)]
-struct Message<'a> {
-    from: &'a str,
-    to: &'a str,
+#[derive(Debug, Clone, Copy)]
+struct Message {
+    from: Id,
+    to: Id,
     pulse: Pulse,
 }
 
-fn run<'a>(sys: &mut System<'a>, conns: &Connections<'a>, mut f: impl FnMut(Message)) {
-    let mut q: Deque<Message, 64> = Deque::new();
-    q.push_back(Message { from: "", to: "broadcaster", pulse: Pulse::Low }).unwrap();
-    while let Some(msg @ Message { from, to, pulse: input }) = q.pop_front() {
-        f(msg);
-        let output = match sys.get_mut(to) {
-            Some(Machine::FlipFlop { on }) if input == Pulse::Low => {
-                *on = !*on;
-                Some(Pulse::from(*on))
-            }
-            Some(Machine::Conjunction { recent }) => {
-                recent.insert(from, input).unwrap();
-                Some(Pulse::from(recent.values().any(|pulse| *pulse != Pulse::High)))
-            }
-            None => Some(input),
-            _ => None,
+// the parsed modules under dense ids, with the names kept only to look them up
+// and report them by
+#[derive(Debug)]
+pub struct Network<'a> {
+    names: Vec<&'a str, MAX_MODULES>,
+    kinds: Vec<Kind, MAX_MODULES>,
+    outputs: Vec<Vec<Id, MAX_OUTPUTS>, MAX_MODULES>,
+    inputs: Vec<Mask, MAX_MODULES>,
+    broadcaster: Id,
+}
+
+// which flip-flops are on, and which inputs each conjunction last heard high
+#[derive(Debug, Clone)]
+struct State {
+    on: Mask,
+    high: [Mask; MAX_MODULES],
+}
+
+impl<'a> Network<'a> {
+    pub fn compile(input: &'a str) -> Self {
+        let mut network = Network {
+            names: Vec::new(),
+            kinds: Vec::new(),
+            outputs: Vec::new(),
+            inputs: Vec::new(),
+            broadcaster: 0,
         };
-        if let Some((pulse, dests)) = output.zip(conns.get(to)) {
-            for dest in dests {
-                q.push_back(Message { from: to, to: dest, pulse }).unwrap();
+        for line in input.lines() {
+            let (label, _) = line.split_once(" -> ").unwrap();
+            let (kind, name) = match label.as_bytes()[0] {
+                b'%' => (Kind::FlipFlop, &label[1..]),
+                b'&' => (Kind::Conjunction, &label[1..]),
+                _ if label == "broadcaster" => (Kind::Broadcaster, label),
+                _ => panic!("invalid module"),
+            };
+            let id = network.intern(name);
+            network.kinds[id as usize] = kind;
+        }
+        for line in input.lines() {
+            let (label, outs) = line.split_once(" -> ").unwrap();
+            let from = network.intern(label.trim_start_matches(['%', '&']));
+            for out in outs.split(", ") {
+                let to = network.intern(out);
+                network.outputs[from as usize].push(to).unwrap();
+                network.inputs[to as usize] |= 1 << from;
+            }
+        }
+        network.broadcaster = network.id("broadcaster").unwrap();
+        network
+    }
+
+    fn intern(&mut self, name: &'a str) -> Id {
+        if let Some(id) = self.id(name) {
+            return id;
+        }
+        self.names.push(name).unwrap();
+        self.kinds.push(Kind::Output).unwrap();
+        self.outputs.push(Vec::new()).unwrap();
+        self.inputs.push(0).unwrap();
+        (self.names.len() - 1) as Id
+    }
+
+    pub fn id(&self, name: &str) -> Option<Id> {
+        self.names.iter().position(|n| *n == name).map(|id| id as Id)
+    }
+
+    pub fn name(&self, id: Id) -> &'a str {
+        self.names[id as usize]
+    }
+
+    pub fn kind(&self, id: Id) -> Kind {
+        self.kinds[id as usize]
+    }
+
+    // the modules sending to this one
+    pub fn inputs(&self, id: Id) -> impl Iterator<Item = Id> + '_ {
+        let mask = self.inputs[id as usize];
+        (0..self.names.len() as Id).filter(move |i| mask >> i & 1 == 1)
+    }
+
+    fn state(&self) -> State {
+        State { on: 0, high: [0; MAX_MODULES] }
+    }
+
+    // pushes the button once
+    fn run(&self, state: &mut State, mut f: impl FnMut(Message)) {
+        let mut q: Deque<Message, 64> = Deque::new();
+        q.push_back(Message { from: self.broadcaster, to: self.broadcaster, pulse: Pulse::Low })
+            .unwrap();
+        while let Some(msg @ Message { from, to, pulse: input }) = q.pop_front() {
+            f(msg);
+            let output = match self.kinds[to as usize] {
+                Kind::Broadcaster => Some(input),
+                Kind::FlipFlop if input == Pulse::Low => {
+                    state.on ^= 1 << to;
+                    Some(Pulse::from(state.on >> to & 1 == 1))
+                }
+                Kind::Conjunction => {
+                    let high = &mut state.high[to as usize];
+                    match input {
+                        Pulse::High => *high |= 1 << from,
+                        Pulse::Low => *high &= !(1 << from),
+                    }
+                    Some(Pulse::from(*high != self.inputs[to as usize]))
+                }
+                Kind::FlipFlop | Kind::Output => None,
+            };
+            if let Some(pulse) = output {
+                for &dest in &self.outputs[to as usize] {
+                    q.push_back(Message { from: to, to: dest, pulse }).unwrap();
+                }
             }
         }
     }
 }
 
-fn count_pulses<'a>(sys: &mut System<'a>, conns: &Connections<'a>) -> (usize, usize) {
+fn count_pulses(network: &Network, state: &mut State) -> (usize, usize) {
     let (mut low, mut high) = (0, 0);
-    run(sys, conns, |Message { pulse, .. }| match pulse {
+    network.run(state, |Message { pulse, .. }| match pulse {
         Pulse::Low => low += 1,
         Pulse::High => high += 1,
     });
     (low, high)
 }
 
-fn run_until<'a>(
-    sys: &mut System<'a>,
-    conns: &Connections<'a>,
-    dest: &'a str,
-    want: Pulse,
-) -> usize {
+fn run_until(network: &Network, dest: Id, want: Pulse) -> usize {
+    let mut state = network.state();
     let mut count = None;
     for presses in 1.. {
-        run(sys, conns, |Message { to, pulse, .. }| {
+        network.run(&mut state, |Message { to, pulse, .. }| {
             if to == dest && pulse == want {
                 count = Some(presses);
             }
@@ -85,64 +180,58 @@ fn run_until<'a>(
     count.unwrap()
 }
 
-fn parse(input: &str) -> (System, Connections) {
-    let mut sys = System::new();
-    let mut conns = Connections::new();
-    for line in input.lines() {
-        let (mut label, out) = line.split_once(" -> ").unwrap();
-        let out = out.split(", ").collect();
-        if label != "broadcaster" {
-            let typ = label.as_bytes()[0];
-            label = &label[1..];
-            sys.insert(
-                label,
-                match typ {
-                    b'&' => Machine::Conjunction { recent: FnvIndexMap::new() },
-                    b'%' => Machine::FlipFlop { on: false },
-                    _ => unreachable!(),
-                },
-            )
-            .unwrap();
-        }
-        conns.insert(label, out).unwrap();
+// how many times part 1 pushes the button
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub presses: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { presses: 1000 }
     }
-    for (dst, machine) in sys.iter_mut() {
-        if let Machine::Conjunction { recent } = machine {
-            for (src, _) in conns.iter().filter(|(_, dsts)| dsts.contains(dst)) {
-                recent.insert(src, Pulse::Low).unwrap();
-            }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["presses"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "presses" => self.presses = params::parse(value)?,
+            _ => return Err(Error::Unknown),
         }
+        Ok(())
     }
-    (sys, conns)
 }
 
-pub fn part1(input: &str) -> usize {
-    let (mut sys, conns) = parse(input);
+pub fn parse(input: &str) -> Network<'_> {
+    Network::compile(input)
+}
+
+pub fn part1_with(network: &Network, params: &Params) -> usize {
+    let mut state = network.state();
     let (mut low, mut high) = (0, 0);
-    for _ in 0..1000 {
-        let (x, y) = count_pulses(&mut sys, &conns);
+    for _ in 0..params.presses {
+        let (x, y) = count_pulses(network, &mut state);
         (low, high) = (low + x, high + y);
     }
     low * high
 }
 
-fn find_source<'a, 'b>(
-    conns: &'b Connections<'a>,
-    of: &'a str,
-) -> impl Iterator<Item = &'a str> + 'b {
-    conns.iter().filter(move |(_, dsts)| dsts.contains(&of)).map(|(src, _)| *src)
+pub fn part1(network: &Network) -> usize {
+    part1_with(network, &Params::default())
 }
 
-pub fn part2(input: &str) -> usize {
-    let (sys, conns) = parse(input);
+pub fn part2(network: &Network) -> usize {
     // this is basically day 8
     // based on manual inspection of the input file: https://bit.ly/3RSUAbq
-    let sink = find_source(&conns, "rx").next().unwrap();
-    let sources: Vec<&str, 4> = find_source(&conns, sink).collect();
-    assert!(matches!(sys.get(sink), Some(Machine::Conjunction { .. })));
-    assert!(sources.iter().all(|src| matches!(sys.get(src), Some(Machine::Conjunction { .. }))));
-    let cycles = sources.iter().map(|src| run_until(&mut sys.clone(), &conns, src, Pulse::Low));
-    cycles.product()
+    let sink = network.inputs(network.id("rx").unwrap()).next().unwrap();
+    let sources: Vec<Id, 4> = network.inputs(sink).collect();
+    for module in sources.iter().chain([&sink]) {
+        let name = network.name(*module);
+        assert_eq!(network.kind(*module), Kind::Conjunction, "{} is not a conjunction", name);
+    }
+    sources.iter().map(|src| run_until(network, *src, Pulse::Low)).product()
 }
 
 #[cfg(test)]
@@ -157,7 +246,10 @@ mod test {
 %c -> inv
 &inv -> a
 ";
-        assert_eq!(part1(input), 32000000);
+        let network = parse(input);
+        assert_eq!(part1(&network), 32000000);
+        // each push sends eight low pulses and four high ones
+        assert_eq!(part1_with(&network, &Params { presses: 1 }), 32);
     }
 
     #[test]
@@ -168,13 +260,13 @@ mod test {
 %b -> con
 &con -> output
 ";
-        assert_eq!(part1(input), 11687500);
-    }
+        let network = parse(input);
+        assert_eq!(part1(&network), 11687500);
 
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day20.txt");
-        assert_eq!(part1(input), 832957356);
-        assert_eq!(part2(input), 240162699605221);
+        let con = network.id("con").unwrap();
+        assert_eq!(network.kind(con), Kind::Conjunction);
+        let inputs: Vec<&str, 2> = network.inputs(con).map(|id| network.name(id)).collect();
+        assert_eq!(inputs, ["a", "b"]);
+        assert_eq!(network.kind(network.id("output").unwrap()), Kind::Output);
     }
 }
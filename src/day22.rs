@@ -1,17 +1,16 @@
 use heapless::Vec;
 
-type Bricks = Vec<Brick, 2048>;
+const MAX_BRICKS: usize = 2048;
+// the heightmap spans the bricks' footprints, up to this many x/y cells
+const MAX_CELLS: usize = 16384;
+const GROUND: u16 = u16::MAX;
+
+pub type Bricks = Vec<Brick, MAX_BRICKS>;
 type Brick = (Pt, Pt);
 type Pt = (i16, i16, i16);
+type Supporters = Vec<u16, 16>;
 
-// adjacency list
-type Overlaps = Vec<Vec<u16, 256>, 2048>;
-
-const ZERO: Pt = (0, 0, 0);
-const REMOVED: Brick = (ZERO, ZERO);
-
-// parse the bricks and return them in ascending sorted order by z-coord
-fn parse(input: &str) -> Bricks {
+pub fn parse(input: &str) -> Bricks {
     let parse_pt = |s: &str| {
         let mut toks = s.split(',');
         let x = toks.next().unwrap().parse::<i16>().unwrap();
@@ -23,99 +22,79 @@ fn parse(input: &str) -> Bricks {
         let (a, b) = line.split_once('~').unwrap();
         (parse_pt(a), parse_pt(b))
     };
-    let mut bricks: Bricks = input.lines().map(parse_brick).collect();
-    bricks.sort_by_key(|brick| brick.0 .2);
-    bricks
+    input.lines().map(parse_brick).collect()
 }
 
-fn compute_intersections(bricks: &Bricks, overlaps: &mut Overlaps) {
-    let has_overlap = |l: &Brick, r: &Brick| {
-        let ix = (l.0 .0.max(r.0 .0), l.1 .0.min(r.1 .0));
-        let iy = (l.0 .1.max(r.0 .1), l.1 .1.min(r.1 .1));
-        ix.0 <= ix.1 && iy.0 <= iy.1
-    };
-    overlaps.clear();
-    overlaps.resize_default(bricks.len()).unwrap();
-    for i in 0..bricks.len() - 1 {
-        for j in i + 1..bricks.len() {
-            if has_overlap(&bricks[i], &bricks[j]) {
-                overlaps[i].push(j as u16).unwrap();
-                overlaps[j].push(i as u16).unwrap();
+// drops the bricks from the lowest up onto a heightmap of the tallest brick
+// over each x/y, returning the order they landed in and which bricks each one
+// came to rest on
+fn settle(bricks: &Bricks) -> (Vec<u16, MAX_BRICKS>, Vec<Supporters, MAX_BRICKS>) {
+    let mut order: Vec<u16, MAX_BRICKS> = (0..bricks.len() as u16).collect();
+    order.sort_unstable_by_key(|i| bricks[*i as usize].0 .2);
+    let mut supporters: Vec<Supporters, MAX_BRICKS> = Vec::new();
+    supporters.resize_default(bricks.len()).unwrap();
+    let width = bricks.iter().map(|(a, b)| a.0.max(b.0) as usize + 1).max().unwrap_or(0);
+    let depth = bricks.iter().map(|(a, b)| a.1.max(b.1) as usize + 1).max().unwrap_or(0);
+    assert!(width * depth <= MAX_CELLS, "footprints too wide");
+    let mut heights: Vec<(i16, u16), MAX_CELLS> = Vec::new();
+    heights.resize(width * depth, (0, GROUND)).unwrap();
+    for &i in &order {
+        let ((x0, y0, z0), (x1, y1, z1)) = bricks[i as usize];
+        let cells =
+            || (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| x as usize * depth + y as usize));
+        let rest = cells().map(|cell| heights[cell].0).max().unwrap();
+        let below = &mut supporters[i as usize];
+        for cell in cells() {
+            let (z, j) = heights[cell];
+            if z == rest && !below.contains(&j) {
+                below.push(j).expect("too many bricks below one");
             }
         }
+        for cell in cells() {
+            heights[cell] = (rest + z1 - z0 + 1, i);
+        }
     }
+    (order, supporters)
 }
 
-fn drop_dist(bricks: &Bricks, i: usize, overlaps: &Overlaps) -> i16 {
-    // find the closest brick in the z-dimension that overlaps with this one
-    // in the x and y dimensions and find the distance. if none exists, we
-    // can fall all the way to the bottom.
-    let above_z = bricks[i].0 .2;
-    overlaps[i]
-        .iter()
-        .map(|j| bricks[*j as usize].1 .2)
-        .filter(|below_z| *below_z < above_z)
-        .map(|below_z| above_z - below_z - 1)
-        .min()
-        .unwrap_or(above_z - 1)
-}
-
-fn drop(bricks: &mut Bricks, i: usize, overlaps: &Overlaps) -> bool {
-    let dz = drop_dist(bricks, i, overlaps);
-    if dz > 0 {
-        bricks[i].0 .2 -= dz;
-        bricks[i].1 .2 -= dz;
-    }
-    dz > 0
-}
-
-fn drop_all(bricks: &mut Bricks, from: usize, to: usize, overlaps: &Overlaps) -> usize {
-    (from..to).map(|i| drop(bricks, i, overlaps) as usize).sum()
-}
-
-fn can_remove(bricks: &mut Bricks, below: usize, overlaps: &Overlaps) -> bool {
-    let brick = bricks[below];
-    // look for a brick that would fall on this one
-    for above in below + 1..bricks.len() {
-        if bricks[above].0 .2 <= brick.1 .2 || !overlaps[above].contains(&(below as u16)) {
-            continue;
+// how many other bricks would fall if each one were removed, by input line.
+// a brick falls when one that all of its paths to the ground go through is
+// removed, so these are the sizes of the subtrees of the dominator tree, in
+// which each brick hangs off the lowest common dominator of its supporters.
+pub fn fall_counts(bricks: &Bricks) -> Vec<usize, MAX_BRICKS> {
+    let (order, supporters) = settle(bricks);
+    let mut idom = [GROUND; MAX_BRICKS];
+    let mut depth = [0; MAX_BRICKS];
+    let depth_of = |depth: &[usize], i: u16| if i == GROUND { 0 } else { depth[i as usize] };
+    for &i in &order {
+        let mut below = supporters[i as usize].iter().copied();
+        let mut dom = below.next().unwrap();
+        for mut other in below {
+            while dom != other {
+                if depth_of(&depth, dom) < depth_of(&depth, other) {
+                    (dom, other) = (other, dom);
+                }
+                dom = idom[dom as usize];
+            }
         }
-        bricks[below] = REMOVED;
-        let d = drop_dist(bricks, above, overlaps);
-        bricks[below] = brick;
-        if d > 0 {
-            return false;
+        idom[i as usize] = dom;
+        depth[i as usize] = depth_of(&depth, dom) + 1;
+    }
+    let mut sizes = [1; MAX_BRICKS];
+    for &i in order.iter().rev() {
+        if idom[i as usize] != GROUND {
+            sizes[idom[i as usize] as usize] += sizes[i as usize];
         }
     }
-    true
+    sizes[..bricks.len()].iter().map(|size| size - 1).collect()
 }
 
-fn remove(bricks: &mut Bricks, i: usize, overlaps: &Overlaps) -> usize {
-    let mut next: Bricks = bricks.clone();
-    next[i] = REMOVED;
-    drop_all(&mut next, i + 1, bricks.len(), overlaps)
+pub fn part1(bricks: &Bricks) -> usize {
+    fall_counts(bricks).iter().filter(|falls| **falls == 0).count()
 }
 
-pub fn part1(input: &str) -> usize {
-    let mut bricks = parse(input);
-    let mut overlaps = Overlaps::new();
-    compute_intersections(&bricks, &mut overlaps);
-
-    let n = bricks.len();
-    drop_all(&mut bricks, 0, n, &overlaps);
-
-    (0..bricks.len()).filter(|i| can_remove(&mut bricks, *i, &overlaps)).count()
-}
-
-pub fn part2(input: &str) -> usize {
-    let mut bricks = parse(input);
-    let mut overlaps = Overlaps::new();
-    compute_intersections(&bricks, &mut overlaps);
-
-    let n = bricks.len();
-    drop_all(&mut bricks, 0, n, &overlaps);
-
-    (0..bricks.len()).map(|i| remove(&mut bricks, i, &overlaps)).sum()
+pub fn part2(bricks: &Bricks) -> usize {
+    fall_counts(bricks).iter().sum()
 }
 
 #[cfg(test)]
@@ -132,11 +111,18 @@ mod test {
 0,1,6~2,1,6
 1,1,8~1,1,9
 ";
-        assert_eq!(part1(input), 5);
-        assert_eq!(part2(input), 7);
+        let bricks = parse(input);
+        assert_eq!(part1(&bricks), 5);
+        assert_eq!(part2(&bricks), 7);
+        assert_eq!(fall_counts(&bricks), [6, 0, 0, 0, 0, 1, 0]);
+    }
 
-        let input = include_str!("../inputs/day22.txt");
-        assert_eq!(part1(input), 403);
-        assert_eq!(part2(input), 70189);
+    #[test]
+    fn test_wide() {
+        let input = "0,0,1~99,0,1
+50,0,2~50,99,2
+50,99,3~50,99,5
+";
+        assert_eq!(fall_counts(&parse(input)), [2, 1, 0]);
     }
 }
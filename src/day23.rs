@@ -1,3 +1,4 @@
+use crate::viz::{Observer, Pos};
 use heapless::{Deque, FnvIndexMap, FnvIndexSet, Vec};
 
 type Tile = u8;
@@ -6,8 +7,22 @@ type Set = FnvIndexSet<Pt, 256>;
 type WeightedGraph = FnvIndexMap<Pt, Vec<(Pt, i16), 4>, 256>;
 type Neighbors = Vec<Pt, 4>;
 
+const MAX_JUNCTIONS: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Wall,
+    Corridor,
+    Junction,
+    Path,
+}
+
+fn pos((r, c): Pt) -> Pos {
+    (r as usize, c as usize)
+}
+
 #[derive(Debug)]
-struct Grid<'a> {
+pub struct Grid<'a> {
     tiles: &'a [u8],
     width: usize,
     height: usize,
@@ -35,6 +50,7 @@ fn find_neighbors<F: Fn(&Grid, Pt) -> Neighbors>(
     grid: &Grid,
     start: Pt,
     nbrs: &F,
+    obs: &mut impl Observer<Cell>,
 ) -> Vec<(Pt, i16), 4> {
     let mut edges: Vec<(Pt, i16), 4> = Vec::new();
     let mut v: FnvIndexSet<Pt, 1024> = FnvIndexSet::new();
@@ -47,14 +63,16 @@ fn find_neighbors<F: Fn(&Grid, Pt) -> Neighbors>(
                 continue;
             }
             // the nodes are the intersections
-            if nbrs(grid, nbr).len() != 2 {
+            if passable_adjacents(grid, nbr).len() != 2 {
                 edges.push((nbr, dist + 1)).unwrap();
                 continue;
             }
             v.insert(nbr).unwrap();
+            obs.cell_changed(pos(nbr), Cell::Corridor);
             q.push_back((nbr, dist + 1)).unwrap();
         }
     }
+    obs.frame();
     edges
 }
 
@@ -64,12 +82,14 @@ fn build_graph<F: Fn(&Grid, Pt) -> Neighbors>(
     graph: &mut WeightedGraph,
     v: &mut Set,
     nbrs: &F,
+    obs: &mut impl Observer<Cell>,
 ) {
     let mut stack: Vec<Pt, 1024> = Vec::new();
     stack.push(cur).unwrap();
     while let Some(cur) = stack.pop() {
+        obs.cell_changed(pos(cur), Cell::Junction);
         let mut edges = Vec::new();
-        for (nbr, dist) in find_neighbors(grid, cur, nbrs) {
+        for (nbr, dist) in find_neighbors(grid, cur, nbrs, obs) {
             edges.push((nbr, dist)).unwrap();
             if !v.contains(&nbr) {
                 v.insert(nbr).unwrap();
@@ -80,29 +100,146 @@ fn build_graph<F: Fn(&Grid, Pt) -> Neighbors>(
     }
 }
 
-fn longest_path_in_graph(graph: &WeightedGraph, cur: Pt, end: Pt, v: &mut Set) -> Option<usize> {
-    let nbrs = graph.get(&cur).unwrap();
-    let dists = nbrs.iter().flat_map(|(nbr, dist_to_nbr)| {
-        if *nbr == end {
-            Some(*dist_to_nbr as usize)
-        } else if v.contains(nbr) {
-            None
-        } else {
-            v.insert(*nbr).unwrap();
-            let dist_from_nbr = longest_path_in_graph(graph, *nbr, end, v);
-            v.remove(nbr);
-            dist_from_nbr.map(|d| *dist_to_nbr as usize + d)
+// the junction graph re-indexed by dense ids, so that a path's visited
+// junctions fit in a u64
+struct JunctionGraph {
+    pts: Vec<Pt, MAX_JUNCTIONS>,
+    edges: Vec<Vec<(u8, u16), 4>, MAX_JUNCTIONS>,
+    // the two longest edges touching each junction, to bound what is left of
+    // a path: a path through a junction uses at most both of them
+    longest: Vec<(u16, u16), MAX_JUNCTIONS>,
+    // the one junction that leads to the end
+    exit: u8,
+    exit_dist: u16,
+}
+
+impl JunctionGraph {
+    fn new(graph: &WeightedGraph, end: Pt) -> Self {
+        assert!(graph.len() <= MAX_JUNCTIONS, "too many junctions");
+        let id = |pt: &Pt| graph.keys().position(|key| key == pt).unwrap() as u8;
+        let mut g = JunctionGraph {
+            pts: graph.keys().copied().collect(),
+            edges: Vec::new(),
+            longest: Vec::new(),
+            exit: 0,
+            exit_dist: 0,
+        };
+        // edges can be one way, so look at both ends of each one
+        let mut touching: Vec<Vec<(u8, u16), 8>, MAX_JUNCTIONS> = Vec::new();
+        touching.resize_default(graph.len()).unwrap();
+        for (pt, nbrs) in graph {
+            let (from, mut edges) = (id(pt), Vec::new());
+            // nothing leaves the end
+            for &(nbr, dist) in nbrs.iter().filter(|_| *pt != end) {
+                if nbr == end {
+                    (g.exit, g.exit_dist) = (from, dist as u16);
+                    continue;
+                }
+                let (to, dist) = (id(&nbr), dist as u16);
+                edges.push((to, dist)).unwrap();
+                for (a, b) in [(from, to), (to, from)] {
+                    if !touching[a as usize].iter().any(|(nbr, _)| *nbr == b) {
+                        touching[a as usize].push((b, dist)).unwrap();
+                    }
+                }
+            }
+            g.edges.push(edges).unwrap();
+        }
+        for mut edges in touching {
+            edges.sort_unstable_by_key(|(_, dist)| u16::MAX - dist);
+            let dist = |i: usize| edges.get(i).map_or(0, |(_, dist)| *dist);
+            g.longest.push((dist(0), dist(1))).unwrap();
         }
-    });
-    dists.max()
+        g
+    }
+
+    fn both(&self, id: u8) -> usize {
+        let (first, second) = self.longest[id as usize];
+        first as usize + second as usize
+    }
 }
 
-fn longest_path<F: Fn(&Grid, Pt) -> Neighbors + Copy>(grid: &Grid, nbrs: F) -> usize {
+// `rest` bounds what the junctions not yet on the path could add, doubled to
+// avoid halves: each of them would be entered and left by one edge at most
+fn longest_path_in_graph(
+    g: &JunctionGraph,
+    cur: u8,
+    dist: usize,
+    visited: u64,
+    rest: usize,
+    best: &mut usize,
+    obs: &mut impl Observer<Cell>,
+) {
+    // going anywhere else from the exit would cut off the end
+    if cur == g.exit {
+        *best = (*best).max(dist + g.exit_dist as usize);
+        return;
+    }
+    let out = g.longest[cur as usize].0 + g.longest[g.exit as usize].0;
+    if 2 * (dist + g.exit_dist as usize) + out as usize + rest <= 2 * *best {
+        return;
+    }
+    for &(nbr, dist_to_nbr) in &g.edges[cur as usize] {
+        if visited & 1 << nbr != 0 {
+            continue;
+        }
+        let nbr_pos = pos(g.pts[nbr as usize]);
+        obs.cell_changed(nbr_pos, Cell::Path);
+        obs.frame();
+        if obs.stopped() {
+            return;
+        }
+        let dist = dist + dist_to_nbr as usize;
+        let rest = if nbr == g.exit { rest } else { rest - g.both(nbr) };
+        longest_path_in_graph(g, nbr, dist, visited | 1 << nbr, rest, best, obs);
+        obs.cell_changed(nbr_pos, Cell::Junction);
+        obs.frame();
+    }
+}
+
+// every path through the junctions, with no bound on what is left, to check
+// the pruned search against. it still stops at the exit, as nothing else could
+fn longest_path_unbounded(g: &JunctionGraph, cur: u8, dist: usize, visited: u64) -> usize {
+    if cur == g.exit {
+        return dist + g.exit_dist as usize;
+    }
+    let nbrs = g.edges[cur as usize].iter().filter(|(nbr, _)| visited & 1 << nbr == 0);
+    nbrs.map(|&(nbr, to)| longest_path_unbounded(g, nbr, dist + to as usize, visited | 1 << nbr))
+        .max()
+        .unwrap_or(0)
+}
+
+fn junction_graph<F: Fn(&Grid, Pt) -> Neighbors + Copy>(
+    grid: &Grid,
+    nbrs: F,
+    obs: &mut impl Observer<Cell>,
+) -> (JunctionGraph, u8) {
     let start = (0, 1);
     let end = (grid.height as i16 - 1, grid.width as i16 - 2);
+    for r in 0..grid.height as i16 {
+        for c in (0..grid.width as i16).filter(|c| grid.get((r, *c)) == b'#') {
+            obs.cell_changed(pos((r, c)), Cell::Wall);
+        }
+    }
+    obs.frame();
     let mut graph = WeightedGraph::new();
-    build_graph(&grid, start, &mut graph, &mut Set::new(), &nbrs);
-    longest_path_in_graph(&graph, start, end, &mut Set::new()).unwrap()
+    build_graph(grid, start, &mut graph, &mut Set::new(), &nbrs, obs);
+    let g = JunctionGraph::new(&graph, end);
+    let start = g.pts.iter().position(|pt| *pt == start).unwrap() as u8;
+    (g, start)
+}
+
+fn longest_path<F: Fn(&Grid, Pt) -> Neighbors + Copy>(
+    grid: &Grid,
+    nbrs: F,
+    obs: &mut impl Observer<Cell>,
+) -> usize {
+    let (g, start) = junction_graph(grid, nbrs, obs);
+    let rest = (0..g.pts.len() as u8).filter(|id| ![start, g.exit].contains(id));
+    let rest = rest.map(|id| g.both(id)).sum();
+    let mut best = 0;
+    longest_path_in_graph(&g, start, 0, 1 << start, rest, &mut best, obs);
+    best
 }
 
 fn passable_adjacents(grid: &Grid, (r, c): Pt) -> Neighbors {
@@ -114,22 +251,52 @@ fn passable_adjacents(grid: &Grid, (r, c): Pt) -> Neighbors {
         .collect()
 }
 
-pub fn part1(input: &str) -> usize {
-    longest_path(&Grid::from(input), |grid, pt @ (r, c)| match grid.get(pt) {
+fn slopes(grid: &Grid, pt @ (r, c): Pt) -> Neighbors {
+    match grid.get(pt) {
         b'.' => passable_adjacents(grid, pt),
         b'^' => Vec::from_slice(&[(r - 1, c)]).unwrap(),
         b'v' => Vec::from_slice(&[(r + 1, c)]).unwrap(),
         b'<' => Vec::from_slice(&[(r, c - 1)]).unwrap(),
         b'>' => Vec::from_slice(&[(r, c + 1)]).unwrap(),
         _ => Vec::new(),
-    })
+    }
 }
 
-pub fn part2(input: &str) -> usize {
-    longest_path(&Grid::from(input), |grid, pt| match grid.get(pt) {
+fn no_slopes(grid: &Grid, pt: Pt) -> Neighbors {
+    match grid.get(pt) {
         b'#' => Vec::new(),
         _ => passable_adjacents(grid, pt),
-    })
+    }
+}
+
+pub fn parse(input: &str) -> Grid<'_> {
+    Grid::from(input)
+}
+
+pub fn part1_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> usize {
+    longest_path(grid, slopes, obs)
+}
+
+pub fn part1(grid: &Grid) -> usize {
+    part1_observed(grid, &mut ())
+}
+
+pub fn part2_observed(grid: &Grid, obs: &mut impl Observer<Cell>) -> usize {
+    longest_path(grid, no_slopes, obs)
+}
+
+pub fn part2(grid: &Grid) -> usize {
+    part2_observed(grid, &mut ())
+}
+
+pub fn part1_unbounded(grid: &Grid) -> usize {
+    let (g, start) = junction_graph(grid, slopes, &mut ());
+    longest_path_unbounded(&g, start, 0, 1 << start)
+}
+
+pub fn part2_unbounded(grid: &Grid) -> usize {
+    let (g, start) = junction_graph(grid, no_slopes, &mut ());
+    longest_path_unbounded(&g, start, 0, 1 << start)
 }
 
 #[cfg(test)]
@@ -161,17 +328,11 @@ mod test {
 #####################.#
 ";
 
-    const REAL_INPUT: &str = include_str!("../inputs/day23.txt");
-
     #[test]
     fn test_example() {
-        assert_eq!(part1(TEST_INPUT), 94);
-        assert_eq!(part2(TEST_INPUT), 154);
-    }
-
-    #[test]
-    fn test_real() {
-        assert_eq!(part1(REAL_INPUT), 2042);
-        assert_eq!(part2(REAL_INPUT), 6466);
+        let grid = parse(TEST_INPUT);
+        assert_eq!(part1(&grid), 94);
+        assert_eq!(part2(&grid), 154);
+        assert_eq!(part2_unbounded(&grid), 154);
     }
 }
@@ -1,12 +1,14 @@
+use crate::params::{self, Error};
+use core::fmt::{self, Write};
 use heapless::Vec;
 use libc_print::std_name::*;
 
-type Systems = Vec<System, 512>;
+pub type Systems = Vec<System, 512>;
 type Ratio = (i128, i128);
 type Pt3 = [i128; 3];
 
 #[derive(Debug)]
-struct System {
+pub struct System {
     x0: Pt3,
     v0: Pt3,
 }
@@ -124,9 +126,9 @@ fn intersection_between(
         let left = (lo - lhs.x0[i]) * tl_bottom;
         let right = (hi - lhs.x0[i]) * tl_bottom;
         let mid = tl_top * lhs.v0[i];
-        if tl_bottom > 0 && !(left <= mid && mid <= right) {
-            return None;
-        } else if tl_bottom < 0 && !(left >= mid && mid >= right) {
+        if (tl_bottom > 0 && !(left <= mid && mid <= right))
+            || (tl_bottom < 0 && !(left >= mid && mid >= right))
+        {
             return None;
         }
     }
@@ -134,7 +136,7 @@ fn intersection_between(
     Some((tl, tr))
 }
 
-fn parse(input: &str) -> Systems {
+pub fn parse(input: &str) -> Systems {
     input.lines().map(System::from).collect()
 }
 
@@ -150,38 +152,76 @@ fn count_intersections(sys: &Systems, lo: i128, hi: i128, dim_lo: usize, dim_hi:
     intersections
 }
 
-pub fn part1(input: &str) -> usize {
-    let (lo, hi) = (200000000000000, 400000000000000);
-    let sys = parse(input);
-    count_intersections(&sys, lo, hi, 0, 1)
+// the test area of part 1, which runs from `lo` to `hi` inclusive in both x
+// and y. set as `window=lo..hi`
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub window: (i128, i128),
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { window: (200000000000000, 400000000000000) }
+    }
+}
+
+impl params::Params for Params {
+    const NAMES: &'static [&'static str] = &["window"];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "window" => self.window = params::parse_range(value)?,
+            _ => return Err(Error::Unknown),
+        }
+        Ok(())
+    }
+}
+
+pub fn part1_with(sys: &Systems, params: &Params) -> usize {
+    let (lo, hi) = params.window;
+    count_intersections(sys, lo, hi, 0, 1)
 }
 
-fn print_python(sys: &Systems) {
-    println!("from z3 import *");
+pub fn part1(sys: &Systems) -> usize {
+    part1_with(sys, &Params::default())
+}
+
+// writes the z3 script that solves part 2
+pub fn part2_script(sys: &Systems, out: &mut impl Write) -> fmt::Result {
+    writeln!(out, "from z3 import *")?;
     for i in 0..3 {
-        println!("x{} = Int('x{}')", i, i);
-        println!("dx{} = Int('dx{}')", i, i);
+        writeln!(out, "x{} = Int('x{}')", i, i)?;
+        writeln!(out, "dx{} = Int('dx{}')", i, i)?;
     }
     for i in 0..sys.len() {
-        println!("t{} = Int('t{}')", i, i);
+        writeln!(out, "t{} = Int('t{}')", i, i)?;
     }
-    println!("s = Solver()");
+    writeln!(out, "s = Solver()")?;
     for (j, line) in sys.iter().enumerate() {
         for i in 0..3 {
-            println!(
+            writeln!(
+                out,
                 "s.add(x{} + dx{} * t{} == {} + {} * t{})",
                 i, i, j, line.x0[i], line.v0[i], j
-            );
+            )?;
         }
     }
-    println!("s.check()");
-    println!("m = s.model()");
-    println!("print(m[x0].as_long() + m[x1].as_long() + m[x2].as_long())");
+    writeln!(out, "s.check()")?;
+    writeln!(out, "m = s.model()")?;
+    writeln!(out, "print(m[x0].as_long() + m[x1].as_long() + m[x2].as_long())")
+}
+
+struct Stdout;
+
+impl Write for Stdout {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        print!("{}", s);
+        Ok(())
+    }
 }
 
-pub fn part2(input: &str) {
-    let sys = parse(input);
-    print_python(&sys);
+pub fn part2(sys: &Systems) {
+    part2_script(sys, &mut Stdout).unwrap();
 }
 
 #[cfg(test)]
@@ -195,16 +235,10 @@ mod test {
 20, 19, 15 @  1, -5, -3
 ";
 
-    const REAL_INPUT: &str = include_str!("../inputs/day24.txt");
-
     #[test]
     fn test_example() {
         let sys = parse(TEST_INPUT);
         assert_eq!(count_intersections(&sys, 7, 27, 0, 1), 2);
-    }
-
-    #[test]
-    fn test_real() {
-        assert_eq!(part1(REAL_INPUT), 15593);
+        assert_eq!(part1_with(&sys, &Params { window: (7, 27) }), 2);
     }
 }
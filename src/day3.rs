@@ -1,98 +1,117 @@
 use crate::lines::{windows, LineWindow};
+use crate::static_vec::StaticVec;
 use core::{iter::Iterator, ops::Range};
-use regex::{CaptureMatches, Match, Regex};
 
-lazy_static! {
-    static ref NUM_RE: Regex = Regex::new(r"\d+").unwrap();
-    static ref SYM_RE: Regex = Regex::new(r"[^.\d]").unwrap();
+// the schematic's lines, each no wider than the gear rows
+pub type Schematic<'a> = StaticVec<&'a str, 256>;
+
+pub fn parse(input: &str) -> Schematic<'_> {
+    let lines = input.lines().inspect(|line| assert!(line.len() <= MAX_WIDTH, "line too wide"));
+    lines.collect()
 }
 
-// Iterator for matches of a pattern around a given string
+// Iterator over the numbers in a line, along with their column ranges
 
-struct Adjacent<'a, 'b> {
-    cur: CaptureMatches<'a, 'b>,
-    above: Option<CaptureMatches<'a, 'b>>,
-    below: Option<CaptureMatches<'a, 'b>>,
-    range: Range<usize>,
+struct Numbers<'a> {
+    bytes: &'a [u8],
+    pos: usize,
 }
 
-fn find_adjacent<'a, 'b>(
-    pat: &'a Regex,
-    w: LineWindow<'b>,
-    range: Range<usize>,
-) -> Adjacent<'a, 'b> {
-    let cur = pat.captures_iter(w.1);
-    let above = w.0.map(|above| pat.captures_iter(above));
-    let below = w.2.map(|below| pat.captures_iter(below));
-    Adjacent { cur, above, below, range }
+fn numbers(line: &str) -> Numbers<'_> {
+    Numbers { bytes: line.as_bytes(), pos: 0 }
 }
 
-impl<'a, 'b> Iterator for Adjacent<'a, 'b> {
-    type Item = Match<'b>;
+impl Iterator for Numbers<'_> {
+    type Item = (Range<usize>, i64);
 
     fn next(&mut self) -> Option<Self::Item> {
-        for cap in &mut self.cur {
-            let cap = cap.get(0).unwrap();
-            if cap.start() == self.range.end || cap.end() == self.range.start {
-                return Some(cap);
-            }
+        while !self.bytes.get(self.pos)?.is_ascii_digit() {
+            self.pos += 1;
         }
-        for caps in [&mut self.above, &mut self.below].into_iter().flatten() {
-            for cap in caps {
-                let cap = cap.get(0).unwrap();
-                if cap.start() <= self.range.end && cap.end() >= self.range.start {
-                    return Some(cap);
-                }
-            }
+        let start = self.pos;
+        let mut num = 0;
+        while let Some(&b @ b'0'..=b'9') = self.bytes.get(self.pos) {
+            num = num * 10 + (b - b'0') as i64;
+            self.pos += 1;
         }
-        None
+        Some((start..self.pos, num))
     }
 }
 
-fn sliding_windows_sum(input: &str, f: impl Fn(LineWindow) -> i64) -> i64 {
-    windows(input).map(f).sum()
+fn sliding_windows_sum(schematic: &Schematic, f: impl Fn(LineWindow) -> i64) -> i64 {
+    windows(schematic.iter().copied()).map(f).sum()
 }
 
-fn parse_num(m: Match) -> i64 {
-    m.as_str().parse().unwrap()
+fn is_symbol(b: u8) -> bool {
+    !b.is_ascii_digit() && b != b'.'
 }
 
-fn is_symbol(ch: char) -> bool {
-    !ch.is_ascii_digit() && ch != '.'
+// columns of the given range plus one on either side, clipped to the line
+fn neighborhood(range: &Range<usize>, len: usize) -> Range<usize> {
+    range.start.saturating_sub(1)..len.min(range.end + 1)
 }
 
-fn has_adj_symbol((above, cur, below): &LineWindow, m: &Match) -> bool {
-    let Range { mut start, mut end } = m.range();
-    start = 1.max(start) - 1;
-    end = cur.len().min(end + 1);
-    let b = cur.as_bytes();
-    if is_symbol(b[start] as char) || is_symbol(b[end - 1] as char) {
-        return true;
-    }
-    [above, below]
+fn has_adj_symbol((above, cur, below): &LineWindow, range: &Range<usize>) -> bool {
+    let cols = neighborhood(range, cur.len());
+    [*above, Some(*cur), *below]
         .into_iter()
         .flatten()
-        .any(|line| line[Range { start, end }].contains(is_symbol))
+        .any(|line| line.as_bytes()[cols.clone()].iter().copied().any(is_symbol))
 }
 
-pub fn part1(input: &str) -> i64 {
-    sliding_windows_sum(input, |w @ (_, cur, _)| {
-        let nums = NUM_RE.captures_iter(cur).map(|m| m.get(0).unwrap());
-        let part_nums = nums.filter(|m| has_adj_symbol(&w, m)).map(parse_num);
-        part_nums.sum()
+pub fn part1(schematic: &Schematic) -> i64 {
+    sliding_windows_sum(schematic, |w @ (_, cur, _)| {
+        let part_nums = numbers(cur).filter(|(range, _)| has_adj_symbol(&w, range));
+        part_nums.map(|(_, num)| num).sum()
     })
 }
 
-pub fn part2(input: &str) -> i64 {
-    sliding_windows_sum(input, |w @ (_, cur, _)| {
-        let gears = cur.chars().enumerate().filter(|p| p.1 == '*').map(|p| p.0);
-        let gear_ratio = |i| {
-            let mut adj_nums = find_adjacent(&NUM_RE, w, i..i + 1).map(parse_num);
-            let ratio = adj_nums.next()? * adj_nums.next()?;
-            Some(ratio).xor(adj_nums.next())
-        };
-        gears.flat_map(gear_ratio).sum()
-    })
+const MAX_WIDTH: usize = 256;
+
+#[derive(Clone, Copy)]
+struct Gear {
+    count: u8,
+    ratio: i64,
+}
+
+const NO_GEAR: Gear = Gear { count: 0, ratio: 1 };
+
+type GearRow = [Gear; MAX_WIDTH];
+
+fn drain_ratios(row: &mut GearRow) -> i64 {
+    let ratios = row.iter().filter(|gear| gear.count == 2).map(|gear| gear.ratio).sum();
+    *row = [NO_GEAR; MAX_WIDTH];
+    ratios
+}
+
+// Each number is visited once and multiplied into every adjacent '*'. The
+// gears of row r are kept in rows[r % 3] and are complete once the numbers
+// of row r + 1 have been visited, at which point the buffer is reused.
+pub fn part2(schematic: &Schematic) -> i64 {
+    let mut rows = [[NO_GEAR; MAX_WIDTH]; 3];
+    let mut total = 0;
+    let mut height = 0;
+    for (i, (above, cur, below)) in windows(schematic.iter().copied()).enumerate() {
+        for (range, num) in numbers(cur) {
+            let cols = neighborhood(&range, cur.len());
+            for (dr, line) in [(0, above), (1, Some(cur)), (2, below)] {
+                let Some(line) = line else { continue };
+                let row = &mut rows[(i + 2 + dr) % 3];
+                for col in cols.clone().filter(|&col| line.as_bytes()[col] == b'*') {
+                    let gear = &mut row[col];
+                    gear.count = gear.count.saturating_add(1);
+                    if gear.count <= 2 {
+                        gear.ratio *= num;
+                    }
+                }
+            }
+        }
+        if i > 0 {
+            total += drain_ratios(&mut rows[(i + 2) % 3]);
+        }
+        height = i + 1;
+    }
+    total + drain_ratios(&mut rows[(height + 2) % 3])
 }
 
 #[cfg(test)]
@@ -112,14 +131,15 @@ mod test {
 ...$.*....
 .664.598..
 ";
-        assert_eq!(part1(input), 4361);
-        assert_eq!(part2(input), 467835);
+        let schematic = parse(input);
+        assert_eq!(part1(&schematic), 4361);
+        assert_eq!(part2(&schematic), 467835);
     }
 
     #[test]
-    fn test2() {
-        let input = include_str!("../inputs/day3.txt");
-        assert_eq!(part1(input), 556057);
-        assert_eq!(part2(input), 82824352);
+    #[should_panic(expected = "line too wide")]
+    fn test_too_wide() {
+        let line = [b'.'; MAX_WIDTH + 1];
+        parse(core::str::from_utf8(&line).unwrap());
     }
 }
@@ -1,4 +1,5 @@
-use core::u128;
+
+use crate::static_vec::StaticVec;
 
 fn parse_nums(s: &str) -> impl Iterator<Item = u8> + '_ {
     s.split_whitespace().flat_map(|tok| tok.parse::<u8>().ok())
@@ -22,15 +23,22 @@ fn card_wins(card: &str) -> (usize, usize) {
     (card_idx, num_wins)
 }
 
-pub fn part1(input: &str) -> usize {
-    let wins = input.lines().map(card_wins).map(|(_, wins)| wins);
+// each card's number and how many of its numbers win
+pub type Cards = StaticVec<(usize, usize), 256>;
+
+pub fn parse(input: &str) -> Cards {
+    input.lines().map(card_wins).collect()
+}
+
+pub fn part1(cards: &Cards) -> usize {
+    let wins = cards.iter().map(|(_, wins)| *wins);
     let scores = wins.map(|wins| if wins == 0 { 0 } else { 1 << (wins - 1) });
     scores.sum()
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(cards: &Cards) -> usize {
     let mut counts = [0usize; 256];
-    for (card, wins) in input.lines().map(card_wins) {
+    for &(card, wins) in cards.iter() {
         counts[card] += 1;
         for i in 0..wins {
             counts[card + i + 1] += counts[card];
@@ -52,14 +60,8 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
 ";
-        assert_eq!(part1(input), 13);
-        assert_eq!(part2(input), 30);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day4.txt");
-        assert_eq!(part1(input), 19855);
-        assert_eq!(part2(input), 10378710);
+        let cards = parse(input);
+        assert_eq!(part1(&cards), 13);
+        assert_eq!(part2(&cards), 30);
     }
 }
@@ -25,35 +25,21 @@ impl Range {
         }
     }
 
-    fn contains(&self, other: &Range) -> bool {
-        self.lo <= other.lo && self.hi >= other.hi
-    }
-
     fn shift(&self, offset: i64) -> Range {
         Range { lo: self.lo + offset, hi: self.hi + offset }
     }
 }
 
-fn parse_seeds(line: &str) -> RangeVec {
-    let mut ranges = RangeVec::empty();
-    for seed in line.split_once(' ').unwrap().1.split(' ') {
-        let lo = seed.parse().unwrap();
-        ranges.push(Range { lo, hi: lo });
-    }
-    ranges
+fn seeds(nums: &Nums) -> RangeVec {
+    nums.iter().map(|&lo| Range { lo, hi: lo }).collect()
 }
 
-fn parse_seed_ranges(line: &str) -> RangeVec {
-    let mut ranges = RangeVec::empty();
-    let mut toks = line.split_once(' ').unwrap().1.split(' ');
-    while let Some(lo) = toks.next() {
-        let lo = lo.parse().unwrap();
-        let len = toks.next().unwrap().parse::<i64>().unwrap();
-        ranges.push(Range { lo, hi: lo + len - 1 });
-    }
-    ranges
+fn seed_ranges(nums: &Nums) -> RangeVec {
+    let pairs = nums[..].chunks(2).map(|pair| (pair[0], pair[1]));
+    pairs.map(|(lo, len)| Range { lo, hi: lo + len - 1 }).collect()
 }
 
+#[derive(Clone, Copy, Default)]
 struct RangeMap {
     src: Range,
     dst: Range,
@@ -69,63 +55,87 @@ fn parse_map(line: &str) -> RangeMap {
     RangeMap { src, dst }
 }
 
-enum Update {
-    NoChange,
-    Moved(Range),
-    Split { unmoved: Range, moved: Range },
+type Nums = StaticVec<i64, 32>;
+type Section = StaticVec<RangeMap, 64>;
+
+// the numbers after "seeds:", which the parts read differently, and the maps
+// of each section after that
+pub struct Almanac {
+    seeds: Nums,
+    sections: StaticVec<Section, 8>,
 }
 
-fn apply_map(range: &Range, RangeMap { src, dst }: &RangeMap) -> Update {
-    if src.contains(range) {
-        Update::Moved(range.shift(dst.lo - src.lo))
-    } else if let Some(intersection) = src.intersection(range) {
-        let moved = intersection.shift(dst.lo - src.lo);
-        let unmoved = if range.lo < intersection.lo {
-            Range { lo: range.lo, hi: intersection.lo - 1 }
-        } else {
-            Range { lo: intersection.hi + 1, hi: range.hi }
-        };
-        Update::Split { unmoved, moved }
-    } else {
-        Update::NoChange
+pub fn parse(input: &str) -> Almanac {
+    let mut sections = input.split("\n\n");
+    let seeds = sections.next().unwrap().split_once(' ').unwrap().1.split(' ');
+    let seeds = seeds.map(|seed| seed.parse().unwrap()).collect();
+    let sections = sections.map(|section| section.lines().skip(1).map(parse_map).collect());
+    Almanac { seeds, sections: sections.collect() }
+}
+
+// the part of `range` the map moves, moved, and the parts on either side of
+// it that the map leaves
+fn apply_map(
+    range: &Range,
+    RangeMap { src, dst }: &RangeMap,
+) -> (Option<Range>, [Option<Range>; 2]) {
+    let Some(intersection) = src.intersection(range) else {
+        return (None, [Some(*range), None]);
+    };
+    let below = Range { lo: range.lo, hi: intersection.lo - 1 };
+    let above = Range { lo: intersection.hi + 1, hi: range.hi };
+    let moved = intersection.shift(dst.lo - src.lo);
+    (Some(moved), [below, above].map(|side| Some(side).filter(|side| side.lo <= side.hi)))
+}
+
+// the same numbers, as few ranges as there can be
+fn merge(mut ranges: RangeVec) -> RangeVec {
+    ranges.sort_unstable_by(Ord::cmp);
+    let mut merged = RangeVec::empty();
+    for range in ranges {
+        match merged.iter_mut().last() {
+            Some(last) if range.lo <= last.hi + 1 => last.hi = last.hi.max(range.hi),
+            _ => merged.push(range),
+        }
     }
+    merged
 }
 
-fn min_location<'a>(ranges: RangeVec, sections: impl Iterator<Item = &'a str>) -> i64 {
-    let (mut ranges, mut scratch) = (ranges, ranges);
+// a section's maps don't overlap, so each takes what it moves out of the
+// ranges that the ones before it left, and the rest stay as they are. the
+// ranges are merged as they go, as the seeds' can overlap and each would be
+// cut up the same way
+fn min_location(ranges: RangeVec, sections: &[Section]) -> i64 {
+    let mut ranges = merge(ranges);
     for section in sections {
-        for map in section.lines().skip(1).map(parse_map) {
-            for i in 0..ranges.len() {
-                match apply_map(&ranges[i], &map) {
-                    Update::NoChange => continue,
-                    Update::Moved(next) => scratch[i] = next,
-                    Update::Split { unmoved, moved } => {
-                        ranges[i] = unmoved;
-                        scratch.push(moved);
-                    }
-                }
+        let mut moved = RangeVec::empty();
+        for map in section.iter() {
+            let mut left = RangeVec::empty();
+            for range in ranges {
+                let (to, sides) = apply_map(&range, map);
+                to.into_iter().for_each(|range| moved.push(range));
+                sides.into_iter().flatten().for_each(|range| left.push(range));
             }
+            ranges = left;
         }
-        ranges = scratch;
+        moved.into_iter().for_each(|range| ranges.push(range));
+        ranges = merge(ranges);
     }
     ranges.into_iter().min().unwrap().lo
 }
 
-pub fn part1(input: &str) -> i64 {
-    let mut sections = input.split("\n\n");
-    let ranges = parse_seeds(sections.next().unwrap());
-    min_location(ranges, sections)
+pub fn part1(almanac: &Almanac) -> i64 {
+    min_location(seeds(&almanac.seeds), &almanac.sections[..])
 }
 
-pub fn part2(input: &str) -> i64 {
-    let mut sections = input.split("\n\n");
-    let ranges = parse_seed_ranges(sections.next().unwrap());
-    min_location(ranges, sections)
+pub fn part2(almanac: &Almanac) -> i64 {
+    min_location(seed_ranges(&almanac.seeds), &almanac.sections[..])
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen;
 
     const INPUT: &str = "seeds: 79 14 55 13
 
@@ -164,18 +174,42 @@ humidity-to-location map:
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(INPUT), 35);
+        assert_eq!(part1(&parse(INPUT)), 35);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(INPUT), 46);
+        assert_eq!(part2(&parse(INPUT)), 46);
+    }
+
+    // every seed, through the map that has it in each section
+    fn brute_force(seeds: impl Iterator<Item = i64>, input: &str) -> i64 {
+        let location = |mut seed: i64| {
+            for section in input.split("\n\n").skip(1) {
+                let map = section.lines().skip(1).map(parse_map);
+                if let Some(map) =
+                    map.into_iter().find(|map| map.src.lo <= seed && seed <= map.src.hi)
+                {
+                    seed += map.dst.lo - map.src.lo;
+                }
+            }
+            seed
+        };
+        seeds.map(location).min().unwrap()
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day5.txt");
-        assert_eq!(part1(input), 322500873);
-        assert_eq!(part2(input), 108956227);
+    fn test_brute_force() {
+        for seed in 0..32 {
+            for size in [1, 2, 4, 8, 12] {
+                let input = gen::input(5, seed, size);
+                let line = input.lines().next().unwrap();
+                let nums = line.split(' ').skip(1).map(|num| num.parse::<i64>().unwrap());
+                let ranges = nums.clone().step_by(2).zip(nums.clone().skip(1).step_by(2));
+                assert_eq!(part1(&parse(&input)), brute_force(nums, &input), "{}", input);
+                let seeds = ranges.flat_map(|(lo, len)| lo..lo + len);
+                assert_eq!(part2(&parse(&input)), brute_force(seeds, &input), "{}", input);
+            }
+        }
     }
 }
@@ -1,8 +1,10 @@
+use crate::static_vec::StaticVec;
+
 fn nums(line: &str) -> impl Iterator<Item = i64> + '_ {
     line.split_whitespace().skip(1).map(|tok| tok.trim().parse::<i64>().unwrap())
 }
 
-fn parse(input: &str) -> impl Iterator<Item = (i64, i64)> + '_ {
+fn parse_races(input: &str) -> impl Iterator<Item = (i64, i64)> + '_ {
     let mut lines = input.lines();
     let times = lines.next().unwrap();
     let dists = lines.next().unwrap();
@@ -20,47 +22,109 @@ fn parse_one(input: &str) -> (i64, i64) {
     (time, dist)
 }
 
+// each race's time and record, and those of the one long race that the bad
+// kerning hides
+pub struct Races {
+    races: StaticVec<(i64, i64), 4>,
+    long: (i64, i64),
+}
+
+pub fn parse(input: &str) -> Races {
+    Races { races: parse_races(input).collect(), long: parse_one(input) }
+}
+
 fn solve(time: i64, dist: i64) -> (f64, f64) {
     // x*(time-x) > dist
     // -x^2 + time*x - dist > 0
     // always two roots and between them it's positive
     let (a, b, c) = (-1.0, time as f64, -dist as f64);
     let discrim = b * b - 4.0 * a * c;
-    let fst = (-b + discrim.sqrt()) / (2.0 * a);
-    let snd = (-b - discrim.sqrt()) / (2.0 * a);
+    let fst = (-b + libm::sqrt(discrim)) / (2.0 * a);
+    let snd = (-b - libm::sqrt(discrim)) / (2.0 * a);
     (fst, snd)
 }
 
+// the roots are only near the first winning time for long races, as a
+// float can't hold their squares, so it's found from there exactly. the
+// winning times are symmetric about half the race
 fn num_pos_solns((time, dist): (i64, i64)) -> i64 {
-    let (lo, hi) = solve(time, dist);
-    (hi - 1.0).ceil() as i64 - (lo + 1.0).floor() as i64 + 1
+    let wins = |held: i64| held as i128 * (time - held) as i128 > dist as i128;
+    if !wins(time / 2) {
+        return 0;
+    }
+    let mut first = (libm::floor(solve(time, dist).0) as i64).clamp(0, time / 2);
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+    while !wins(first) {
+        first += 1;
+    }
+    time - 2 * first + 1
 }
 
-pub fn part1(input: &str) -> i64 {
-    parse(input).map(num_pos_solns).product()
+pub fn part1(races: &Races) -> i64 {
+    races.races.iter().copied().map(num_pos_solns).product()
 }
 
-pub fn part2(input: &str) -> i64 {
-    num_pos_solns(parse_one(input))
+pub fn part2(races: &Races) -> i64 {
+    num_pos_solns(races.long)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::gen;
 
     #[test]
     fn test_examples() {
         let input = "Time:      7  15   30
 Distance:  9  40  200
 ";
-        assert_eq!(part1(input), 288);
-        assert_eq!(part2(input), 71503);
+        let races = parse(input);
+        assert_eq!(part1(&races), 288);
+        assert_eq!(part2(&races), 71503);
+    }
+
+    // every time the button could be held for
+    fn brute_force((time, dist): (i64, i64)) -> i64 {
+        (0..=time).filter(|held| held * (time - held) > dist).count() as i64
+    }
+
+    #[test]
+    fn test_brute_force() {
+        // including the records that are tied at whole numbers of ms
+        for time in 0..100 {
+            for dist in 0..time * time / 4 + 2 {
+                assert_eq!(
+                    num_pos_solns((time, dist)),
+                    brute_force((time, dist)),
+                    "{} {}",
+                    time,
+                    dist
+                );
+            }
+        }
+        for seed in 0..32 {
+            for size in [1, 2] {
+                let input = gen::input(6, seed, size);
+                let races = parse(&input);
+                let product = races.races.iter().copied().map(brute_force).product();
+                assert_eq!(part1(&races), product, "{}", input);
+                assert_eq!(part2(&races), brute_force(races.long), "{}", input);
+            }
+        }
     }
 
     #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day6.txt");
-        assert_eq!(part1(input), 32076);
-        assert_eq!(part2(input), 34278221);
+    fn test_long_races() {
+        // records tied at a whole time, and one short of them, in races too
+        // long for the brute force or for floats to find the roots exactly
+        for time in [49_999_999, 50_000_000, 94_000_001, 3_000_000_000] {
+            for held in (1..time / 2).step_by(999_983) {
+                let tied = held * (time - held);
+                assert_eq!(num_pos_solns((time, tied)), time - 2 * held - 1);
+                assert_eq!(num_pos_solns((time, tied - 1)), time - 2 * held + 1);
+            }
+        }
     }
 }
@@ -85,7 +85,7 @@ impl HandType {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
-struct Hand([Card; 5]);
+pub struct Hand([Card; 5]);
 
 impl Hand {
     fn counts(&self) -> StaticVec<i8, 13> {
@@ -149,21 +149,26 @@ fn parse_hand(s: &str) -> Hand {
     Hand(hand)
 }
 
-fn total_winnings(input: &str, cmp_hands: impl Fn(&Hand, &Hand) -> Ordering) -> i64 {
-    let mut hands = StaticVec::<(Hand, i64), 1024>::empty();
-    for (hand, bid) in input.lines().map(|line| line.split_once(' ').unwrap()) {
-        hands.push((parse_hand(hand), bid.parse().unwrap()));
-    }
-    hands.sort_by(|l, r| cmp_hands(&l.0, &r.0));
+// each hand and its bid
+pub type Hands = StaticVec<(Hand, i64), 1024>;
+
+pub fn parse(input: &str) -> Hands {
+    let hands = input.lines().map(|line| line.split_once(' ').unwrap());
+    hands.map(|(hand, bid)| (parse_hand(hand), bid.parse().unwrap())).collect()
+}
+
+fn total_winnings(hands: &Hands, cmp_hands: impl Fn(&Hand, &Hand) -> Ordering) -> i64 {
+    let mut hands = *hands;
+    hands.sort_unstable_by(|l, r| cmp_hands(&l.0, &r.0));
     (0..hands.len()).map(|place| (place + 1) as i64 * hands[place].1).sum()
 }
 
-pub fn part1(input: &str) -> i64 {
-    total_winnings(input, make_cmp(Hand::typ, Card::score))
+pub fn part1(hands: &Hands) -> i64 {
+    total_winnings(hands, make_cmp(Hand::typ, Card::score))
 }
 
-pub fn part2(input: &str) -> i64 {
-    total_winnings(input, make_cmp(Hand::typ_joker, Card::score_joker))
+pub fn part2(hands: &Hands) -> i64 {
+    total_winnings(hands, make_cmp(Hand::typ_joker, Card::score_joker))
 }
 
 #[cfg(test)]
@@ -187,14 +192,8 @@ KK677 28
 KTJJT 220
 QQQJA 483
 ";
-        assert_eq!(part1(input), 6440);
-        assert_eq!(part2(input), 5905);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day7.txt");
-        assert_eq!(part1(input), 248217452);
-        assert_eq!(part2(input), 245576185);
+        let hands = parse(input);
+        assert_eq!(part1(&hands), 6440);
+        assert_eq!(part2(&hands), 5905);
     }
 }
@@ -5,7 +5,14 @@ type Graph<'a> = StaticVec<(&'a str, (&'a str, &'a str)), 1024>;
 type IndexedGraph = StaticVec<(usize, usize), 1024>;
 type IndexedKeys<'a> = StaticVec<&'a str, 1024>;
 
-fn parse(input: &str) -> (&[Dir], IndexedGraph, IndexedKeys) {
+// the directions to take, and the nodes by index with their keys alongside
+pub struct Map<'a> {
+    dirs: &'a [Dir],
+    graph: IndexedGraph,
+    keys: IndexedKeys<'a>,
+}
+
+pub fn parse(input: &str) -> Map<'_> {
     let mut lines = input.lines();
     let dirs = lines.next().unwrap().as_bytes();
 
@@ -16,7 +23,7 @@ fn parse(input: &str) -> (&[Dir], IndexedGraph, IndexedKeys) {
         let (left, right) = to.split_once(", ").unwrap();
         graph.push((from, (&left[1..], &right[..right.len() - 1])));
     }
-    graph.sort_by(|(left, _), (right, _)| left.cmp(right));
+    graph.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
 
     // reduce to an index graph with a sidetable of keys to avoid online
     // binary searches
@@ -29,7 +36,7 @@ fn parse(input: &str) -> (&[Dir], IndexedGraph, IndexedKeys) {
         keys.push(key);
     }
 
-    (dirs, indexed_graph, keys)
+    Map { dirs, graph: indexed_graph, keys }
 }
 
 fn dist(from: usize, to: impl Fn(usize) -> bool, dirs: &[Dir], g: &IndexedGraph) -> i64 {
@@ -49,11 +56,11 @@ fn dist(from: usize, to: impl Fn(usize) -> bool, dirs: &[Dir], g: &IndexedGraph)
     steps
 }
 
-pub fn part1(input: &str) -> i64 {
-    let (dirs, graph, keys) = parse(input);
+pub fn part1(map: &Map) -> i64 {
+    let Map { dirs, graph, keys } = map;
     let start = keys.binary_search_by_key(&"AAA", |s| *s).unwrap();
     let end = keys.binary_search_by_key(&"ZZZ", |s| *s).unwrap();
-    dist(start, |cur| cur == end, dirs, &graph)
+    dist(start, |cur| cur == end, dirs, graph)
 }
 
 fn gcd(x: i64, y: i64) -> i64 {
@@ -72,11 +79,11 @@ fn ends_with(s: &str, c: u8) -> bool {
     s.as_bytes()[s.len() - 1] == c
 }
 
-pub fn part2(input: &str) -> i64 {
-    let (dirs, graph, keys) = parse(input);
+pub fn part2(map: &Map) -> i64 {
+    let Map { dirs, graph, keys } = map;
     let starts = (0..graph.len()).filter(|i| ends_with(keys[*i], b'A'));
     starts.fold(1, |total, start| {
-        lcm(total, dist(start, |cur| ends_with(keys[cur], b'Z'), dirs, &graph))
+        lcm(total, dist(start, |cur| ends_with(keys[cur], b'Z'), dirs, graph))
     })
 }
 
@@ -96,7 +103,7 @@ EEE = (EEE, EEE)
 GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 ";
-        assert_eq!(part1(input), 2);
+        assert_eq!(part1(&parse(input)), 2);
     }
 
     #[test]
@@ -107,7 +114,7 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 ";
-        assert_eq!(part1(input), 6);
+        assert_eq!(part1(&parse(input)), 6);
     }
 
     #[test]
@@ -123,13 +130,6 @@ ZZZ = (ZZZ, ZZZ)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)
 ";
-        assert_eq!(part2(input), 6);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day8.txt");
-        assert_eq!(part1(input), 19783);
-        assert_eq!(part2(input), 9177460370549);
+        assert_eq!(part2(&parse(input)), 6);
     }
 }
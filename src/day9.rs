@@ -1,4 +1,7 @@
-type Seq = crate::static_vec::StaticVec<i64, 32>;
+use crate::static_vec::StaticVec;
+
+type Seq = StaticVec<i64, 32>;
+pub type Seqs = StaticVec<Seq, 256>;
 
 fn next(seq: Seq) -> i64 {
     if seq.iter().all(|x| *x == 0) {
@@ -20,16 +23,20 @@ fn diffs(seq: &Seq) -> Seq {
     (1..seq.len()).map(|i| seq[i] - seq[i - 1]).collect()
 }
 
-fn parse(line: &str) -> Seq {
+fn parse_seq(line: &str) -> Seq {
     line.split_whitespace().map(|tok| tok.parse::<i64>().unwrap()).collect()
 }
 
-pub fn part1(input: &str) -> i64 {
-    input.lines().map(parse).map(next).sum()
+pub fn parse(input: &str) -> Seqs {
+    input.lines().map(parse_seq).collect()
+}
+
+pub fn part1(seqs: &Seqs) -> i64 {
+    seqs.iter().copied().map(next).sum()
 }
 
-pub fn part2(input: &str) -> i64 {
-    input.lines().map(parse).map(prev).sum()
+pub fn part2(seqs: &Seqs) -> i64 {
+    seqs.iter().copied().map(prev).sum()
 }
 
 #[cfg(test)]
@@ -42,14 +49,8 @@ mod test {
 1 3 6 10 15 21
 10 13 16 21 30 45
 ";
-        assert_eq!(part1(input), 114);
-        assert_eq!(part2(input), 2);
-    }
-
-    #[test]
-    fn test_real() {
-        let input = include_str!("../inputs/day9.txt");
-        assert_eq!(part1(input), 1581679977);
-        assert_eq!(part2(input), 889);
+        let seqs = parse(input);
+        assert_eq!(part1(&seqs), 114);
+        assert_eq!(part2(&seqs), 2);
     }
 }
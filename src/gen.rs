@@ -0,0 +1,1079 @@
+// random puzzle inputs of any size, for tests that want more than the examples
+// and for timing how the solvers scale. each generator writes a well-formed
+// input for its day (one that has the properties the puzzle promises and the
+// solvers rely on) with about `size` of the day's items (lines, galaxies,
+// bricks, ...), picked by the `Rng`, so the same seed and size always give the
+// same input
+use core::fmt::{self, Write};
+use heapless::Vec;
+
+// splitmix64: https://prng.di.unimi.it/splitmix64.c
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let z = (self.0 ^ (self.0 >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // in `0..n`, with a bias too small to matter here
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    // in `lo..hi`
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + self.below((hi - lo) as u64) as i64
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+pub struct Generator {
+    pub day: u8,
+    // about the size of the real input
+    pub size: usize,
+    // the largest size whose input the solvers have room for and finish in seconds
+    pub max_size: usize,
+    pub generate: fn(&mut Rng, usize, &mut dyn Write) -> fmt::Result,
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator { day: 1, size: 1000, max_size: 1024, generate: day1 },
+    Generator { day: 2, size: 100, max_size: 128, generate: day2 },
+    Generator { day: 3, size: 140, max_size: 256, generate: day3 },
+    Generator { day: 4, size: 200, max_size: 255, generate: day4 },
+    Generator { day: 5, size: 12, max_size: 12, generate: day5 },
+    Generator { day: 6, size: 4, max_size: 4, generate: day6 },
+    Generator { day: 7, size: 1000, max_size: 1024, generate: day7 },
+    Generator { day: 8, size: 750, max_size: 800, generate: day8 },
+    Generator { day: 9, size: 200, max_size: 256, generate: day9 },
+    Generator { day: 10, size: 128, max_size: 128, generate: day10 },
+    Generator { day: 11, size: 450, max_size: 3000, generate: day11 },
+    Generator { day: 12, size: 1000, max_size: 1024, generate: day12 },
+    Generator { day: 13, size: 100, max_size: 128, generate: day13 },
+    Generator { day: 14, size: 100, max_size: 128, generate: day14 },
+    Generator { day: 15, size: 2048, max_size: 2048, generate: day15 },
+    Generator { day: 16, size: 110, max_size: 110, generate: day16 },
+    Generator { day: 17, size: 141, max_size: 256, generate: day17 },
+    Generator { day: 18, size: 700, max_size: 1000, generate: day18 },
+    Generator { day: 19, size: 200, max_size: 200, generate: day19 },
+    Generator { day: 20, size: 12, max_size: 12, generate: day20 },
+    Generator { day: 21, size: 131, max_size: 131, generate: day21 },
+    Generator { day: 22, size: 1200, max_size: 2048, generate: day22 },
+    Generator { day: 23, size: 6, max_size: 6, generate: day23 },
+    Generator { day: 24, size: 300, max_size: 512, generate: day24 },
+    Generator { day: 25, size: 1500, max_size: 1500, generate: day25 },
+];
+
+pub fn find(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+// a day's input for the tests
+#[cfg(test)]
+pub(crate) fn input(day: u8, seed: u64, size: usize) -> heapless::String<65536> {
+    let mut input = heapless::String::new();
+    (find(day).unwrap().generate)(&mut Rng::new(seed), size, &mut input).unwrap();
+    input
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
+
+fn digit(rng: &mut Rng) -> char {
+    (b'1' + rng.below(9) as u8) as char
+}
+
+fn is_prime(n: usize) -> bool {
+    n > 1 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+// the corners of a random polygon on a grid `width` squares wide and `height`
+// tall, as (row, col), clockwise from the top left. it's every square between
+// a top and a bottom that move up and down a little from column to column,
+// always overlapping the last column's, so its boundary is a single simple
+// loop. `width` can be at most 256
+fn polygon(rng: &mut Rng, width: usize, height: usize) -> Vec<(usize, usize), 1024> {
+    let (mut tops, mut bottoms) = (Vec::<usize, 256>::new(), Vec::<usize, 256>::new());
+    let mut top = rng.below(height as u64 / 2) as usize;
+    let mut bottom = height - rng.below(height as u64 / 2) as usize;
+    for col in 0..width {
+        if col > 0 {
+            loop {
+                let next_top = (top as i64 + rng.range(-2, 3)).clamp(0, height as i64 - 1) as usize;
+                let next_bottom =
+                    (bottom as i64 + rng.range(-2, 3)).clamp(1, height as i64) as usize;
+                if next_top.max(top) < next_bottom.min(bottom) {
+                    (top, bottom) = (next_top, next_bottom);
+                    break;
+                }
+            }
+        }
+        tops.push(top).unwrap();
+        bottoms.push(bottom).unwrap();
+    }
+    // dropping the repeats and the corners that are only on a straight side
+    let mut corners: Vec<(usize, usize), 1024> = Vec::new();
+    let mut push = |pt: (usize, usize)| match corners[..] {
+        [.., last] if last == pt => {}
+        [.., a, b] if (a.0 == b.0 && b.0 == pt.0) || (a.1 == b.1 && b.1 == pt.1) => {
+            *corners.last_mut().unwrap() = pt
+        }
+        _ => corners.push(pt).unwrap(),
+    };
+    for col in 0..width {
+        push((tops[col], col));
+        push((tops[col], col + 1));
+    }
+    for col in (0..width).rev() {
+        push((bottoms[col], col + 1));
+        push((bottoms[col], col));
+    }
+    corners
+}
+
+const DIGIT_NAMES: [&str; 9] =
+    ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// `size` lines of letters with digits and the names of digits mixed in, each
+// with at least one digit
+fn day1(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size {
+        let len = rng.range(1, 12) as u64;
+        let at = rng.below(len);
+        for i in 0..len {
+            match rng.below(6) {
+                _ if i == at => out.write_char(digit(rng))?,
+                0 => out.write_char(digit(rng))?,
+                1 => out.write_str(rng.pick(&DIGIT_NAMES))?,
+                _ => out.write_char(letter(rng))?,
+            }
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` games of up to six draws of up to 20 cubes of each color
+fn day2(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for game in 1..=size {
+        write!(out, "Game {}:", game)?;
+        for draw in 0..rng.range(1, 7) {
+            let mut colors = ["red", "green", "blue"];
+            rng.shuffle(&mut colors);
+            for (i, color) in colors[..rng.range(1, 4) as usize].iter().enumerate() {
+                let sep = match (i, draw) {
+                    (0, 0) => "",
+                    (0, _) => ";",
+                    _ => ",",
+                };
+                write!(out, "{} {} {}", sep, rng.range(1, 21), color)?;
+            }
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// a `size` by `size` schematic with numbers of up to three digits and symbols
+// scattered over it
+fn day3(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let symbol = |rng: &mut Rng| rng.pick(b"**#+$/@%=&-") as char;
+    for _ in 0..size {
+        let mut col = 0;
+        while col < size {
+            match rng.below(8) {
+                0 => {
+                    let digits = (rng.range(1, 4) as usize).min(size - col);
+                    let lo = 10i64.pow(digits as u32 - 1);
+                    write!(out, "{}", rng.range(lo, lo * 10))?;
+                    col += digits;
+                    // and something that isn't a digit after it
+                    if col < size {
+                        out.write_char(if rng.below(4) == 0 { symbol(rng) } else { '.' })?;
+                        col += 1;
+                    }
+                    continue;
+                }
+                1 => out.write_char(symbol(rng))?,
+                _ => out.write_char('.')?,
+            }
+            col += 1;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` cards of ten winning numbers and 25 numbers you have. like the real
+// input, they come in runs where no card wins any past the end of the run, so
+// the copies only multiply so far
+fn day4(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let mut nums: [u8; 99] = core::array::from_fn(|i| i as u8 + 1);
+    let mut last = 0;
+    for card in 1..=size {
+        if card > last {
+            last = (card + rng.range(3, 15) as usize).min(size);
+        }
+        rng.shuffle(&mut nums);
+        // the first ten are the winning numbers, and you have some of them and
+        // some of the others
+        let wins = rng.below((last - card).min(10) as u64 + 1) as usize;
+        let mut have = [0; 25];
+        have[..wins].copy_from_slice(&nums[..wins]);
+        have[wins..].copy_from_slice(&nums[10..35 - wins]);
+        rng.shuffle(&mut have);
+        write!(out, "Card {:>3}:", card)?;
+        for num in &nums[..10] {
+            write!(out, " {:>2}", num)?;
+        }
+        out.write_str(" |")?;
+        for num in &have {
+            write!(out, " {:>2}", num)?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+const DAY5_MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+// ranges of seeds, and maps that each cut the numbers from zero up into
+// `size` blocks (at most 12, so the solver has room for the pieces the
+// ranges are cut into) and shuffle them
+fn day5(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let blocks = size.clamp(1, 12);
+    let limit = 5 * (blocks * blocks) as u64 + 1;
+    out.write_str("seeds:")?;
+    for _ in 0..(blocks / 2 + 1).min(10) {
+        let start = rng.below(limit);
+        write!(out, " {} {}", start, 1 + rng.below(limit - start))?;
+    }
+    out.write_char('\n')?;
+    for name in DAY5_MAPS {
+        let mut lens = [0; 12];
+        let (mut srcs, mut dsts) = ([0; 12], [0; 12]);
+        let mut order: [usize; 12] = core::array::from_fn(|i| i);
+        for block in 0..blocks {
+            lens[block] = rng.range(1, 10 * blocks as i64 + 1);
+            srcs[block] = if block > 0 { srcs[block - 1] + lens[block - 1] } else { 0 };
+        }
+        rng.shuffle(&mut order[..blocks]);
+        let mut at = 0;
+        for &block in &order[..blocks] {
+            dsts[block] = at;
+            at += lens[block];
+        }
+        // listed in some other order
+        rng.shuffle(&mut order[..blocks]);
+        write!(out, "\n{} map:\n", name)?;
+        for &block in &order[..blocks] {
+            writeln!(out, "{} {} {}", dsts[block], srcs[block], lens[block])?;
+        }
+    }
+    Ok(())
+}
+
+// `size` races (at most four, so that the one long race fits) of under 100ms,
+// each with a record that can be beaten, as can the long race's
+fn day6(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let concat = |a: i128, b: i64| {
+        let mut pow = 10;
+        while pow <= b {
+            pow *= 10;
+        }
+        a * pow as i128 + b as i128
+    };
+    let races = size.clamp(1, 4);
+    loop {
+        let (mut times, mut dists) = ([0; 4], [0; 4]);
+        let (mut time, mut dist) = (0, 0);
+        for race in 0..races {
+            let t = rng.range(7, 100);
+            times[race] = t;
+            dists[race] = rng.range(t * t / 8, (t / 2) * (t - t / 2));
+            (time, dist) = (concat(time, t), concat(dist, dists[race]));
+        }
+        if dist < (time / 2) * (time - time / 2) {
+            out.write_str("Time:    ")?;
+            for t in &times[..races] {
+                write!(out, "{:>7}", t)?;
+            }
+            out.write_str("\nDistance:")?;
+            for d in &dists[..races] {
+                write!(out, "{:>7}", d)?;
+            }
+            return out.write_char('\n');
+        }
+    }
+}
+
+// `size` different hands (at most 1024) with bids up to 1000
+fn day7(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let mut hands: Vec<[u8; 5], 1024> = Vec::new();
+    while hands.len() < size.min(1024) {
+        let hand = core::array::from_fn(|_| rng.pick(b"23456789TJQKA"));
+        if !hands.contains(&hand) {
+            hands.push(hand).unwrap();
+            let hand = core::str::from_utf8(&hand).unwrap();
+            writeln!(out, "{} {}", hand, rng.range(1, 1001))?;
+        }
+    }
+    Ok(())
+}
+
+// up to six ghosts' loops through about `size` nodes in all (and no more than
+// the solver's 1024). as in the real input, each loop's length is a multiple
+// of the number of directions (by a prime, a different one for each), and its
+// end leads where its start does, so the steps to the end repeat and the least
+// common multiple is right. the first loop goes from `AAA` to `ZZZ`, and the
+// turns off them lead to random nodes
+fn day8(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let turns = rng.range(2, 12) as usize;
+    let ghosts = rng.range(1, 7) as usize;
+    let mut lens: Vec<usize, 6> = Vec::new();
+    let mut prime = (size / (ghosts * turns)).max(2);
+    let mut total = 0;
+    while lens.len() < ghosts {
+        if is_prime(prime) {
+            if !lens.is_empty() && total + lens.len() + turns * prime >= 1024 {
+                break;
+            }
+            lens.push(turns * prime).unwrap();
+            total += turns * prime;
+        }
+        prime += 1;
+    }
+    let dirs: [u8; 12] = core::array::from_fn(|_| rng.pick(b"LR"));
+    write!(out, "{}\n\n", core::str::from_utf8(&dirs[..turns]).unwrap())?;
+    // the nodes between each loop's start and end, which end in neither A nor Z
+    let inner = total - lens.len();
+    let name =
+        |i: usize| [b'A' + (i / 624 % 26) as u8, b'A' + (i / 24 % 26) as u8, b'B' + (i % 24) as u8];
+    let mut first_inner = 0;
+    for (ghost, &len) in lens.iter().enumerate() {
+        let ghost = ghost as u8;
+        let node = |j: usize| match j {
+            0 => [b'A' + ghost, b'A' + ghost, b'A'],
+            _ if j == len => [b'Z' - ghost, b'Z' - ghost, b'Z'],
+            _ => name(first_inner + j - 1),
+        };
+        let mut start = ([0; 3], [0; 3]);
+        for j in 0..=len {
+            let (left, right) = if j == len {
+                start
+            } else {
+                let (next, other) = (node(j + 1), name(rng.below(inner as u64) as usize));
+                if dirs[j % turns] == b'L' {
+                    (next, other)
+                } else {
+                    (other, next)
+                }
+            };
+            if j == 0 {
+                start = (left, right);
+            }
+            let [from, left, right] = [node(j), left, right];
+            writeln!(
+                out,
+                "{} = ({}, {})",
+                core::str::from_utf8(&from).unwrap(),
+                core::str::from_utf8(&left).unwrap(),
+                core::str::from_utf8(&right).unwrap()
+            )?;
+        }
+        first_inner += len - 1;
+    }
+    Ok(())
+}
+
+// `size` sequences of up to 21 values of polynomials of degree at most six,
+// with small coefficients
+fn day9(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size {
+        let len = rng.range(5, 22);
+        let degree = rng.below(7.min(len as u64 - 1)) as usize;
+        let coeffs: [i64; 7] = core::array::from_fn(|_| rng.range(-9, 10));
+        for x in 0..len {
+            let value = coeffs[..=degree].iter().rev().fold(0, |acc, c| acc * x + c);
+            write!(out, "{}{}", if x > 0 { " " } else { "" }, value)?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// a `size` by `size` field (at most 128, so that the loop and what's inside
+// it fit the solver's sets) of random pipes, with a loop around a random
+// polygon (see `polygon`) through the start. nothing else connects to the
+// start, and it isn't the loop's top left corner, where the solver starts
+// walking it
+fn day10(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let side = size.clamp(5, 128);
+    let mut grid = [[b'.'; 128]; 128];
+    for row in &mut grid[..side] {
+        for tile in &mut row[..side] {
+            *tile = rng.pick(b"|-LJ7F....");
+        }
+    }
+    let corners = polygon(rng, side - 3, side - 3);
+    let mut looop: Vec<(usize, usize), 16384> = Vec::new();
+    for (i, &(row, col)) in corners.iter().enumerate() {
+        let (next_row, next_col) = corners[(i + 1) % corners.len()];
+        let (mut r, mut c) = (row, col);
+        while (r, c) != (next_row, next_col) {
+            looop.push((r + 1, c + 1)).unwrap();
+            r = if next_row > r {
+                r + 1
+            } else if next_row < r {
+                r - 1
+            } else {
+                r
+            };
+            c = if next_col > c {
+                c + 1
+            } else if next_col < c {
+                c - 1
+            } else {
+                c
+            };
+        }
+    }
+    let mut on_loop = [[false; 128]; 128];
+    for (i, &(row, col)) in looop.iter().enumerate() {
+        let (prev, next) =
+            (looop[(i + looop.len() - 1) % looop.len()], looop[(i + 1) % looop.len()]);
+        let up = prev.0 < row || next.0 < row;
+        let down = prev.0 > row || next.0 > row;
+        let left = prev.1 < col || next.1 < col;
+        grid[row][col] = match (up, down, left) {
+            (true, true, _) => b'|',
+            (true, _, true) => b'J',
+            (true, _, false) => b'L',
+            (_, true, true) => b'7',
+            (_, true, false) => b'F',
+            _ => b'-',
+        };
+        on_loop[row][col] = true;
+    }
+    let top_left = *looop.iter().min().unwrap();
+    let start = loop {
+        let pt = rng.pick(&looop);
+        if pt != top_left {
+            break pt;
+        }
+    };
+    grid[start.0][start.1] = b'S';
+    let (row, col) = start;
+    for (r, c, toward) in [
+        (row - 1, col, b"|7F"),
+        (row + 1, col, b"|LJ"),
+        (row, col - 1, b"-LF"),
+        (row, col + 1, b"-J7"),
+    ] {
+        if !on_loop[r][c] && toward.contains(&grid[r][c]) {
+            grid[r][c] = b'.';
+        }
+    }
+    for row in &grid[..side] {
+        writeln!(out, "{}", core::str::from_utf8(&row[..side]).unwrap())?;
+    }
+    Ok(())
+}
+
+// `size` galaxies, give or take, at the same density as the real input, with
+// every eighth row and column left empty to be expanded
+fn day11(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let mut side = 2;
+    while side * side < 8 * size {
+        side += 1;
+    }
+    let open = |i: usize| i % 8 != 7;
+    let cells = (0..side).filter(|i| open(*i)).count().pow(2) as u64;
+    for row in 0..side {
+        for col in 0..side {
+            let galaxy = open(row) && open(col) && rng.below(cells) < size as u64;
+            out.write_char(if galaxy { '#' } else { '.' })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` rows of up to 20 springs, some of them broken, with about half of
+// them then forgotten
+fn day12(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size {
+        let len = rng.range(1, 21) as usize;
+        let mut springs = [b'.'; 20];
+        for spring in &mut springs[..len] {
+            if rng.below(2) == 0 {
+                *spring = b'#';
+            }
+        }
+        if !springs[..len].contains(&b'#') {
+            springs[rng.below(len as u64) as usize] = b'#';
+        }
+        for &spring in &springs[..len] {
+            out.write_char(if rng.below(2) == 0 { '?' } else { spring as char })?;
+        }
+        let (mut run, mut sep) = (0, " ");
+        for &spring in springs[..len].iter().chain(b".") {
+            if spring == b'#' {
+                run += 1;
+            } else if run > 0 {
+                write!(out, "{}{}", sep, run)?;
+                (run, sep) = (0, ",");
+            }
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// the tiles that differ between the rows (as bits) on either side of each line
+// between columns `1..width`, which a pattern's reflection in rows shouldn't
+// come close to having
+fn column_diffs(rows: &[u32], width: usize, line: usize) -> u32 {
+    let cols = line.min(width - line);
+    let mirror =
+        |row: u32| (0..cols).fold(0, |acc, i| acc | (row >> (line + i) & 1) << (line - 1 - i));
+    rows.iter()
+        .map(|&row| {
+            ((row ^ mirror(row)) & ((1 << line) - 1) & !((1 << (line - cols)) - 1)).count_ones()
+        })
+        .sum()
+}
+
+// `size` patterns, each reflected in one line and smudged so it's one tile off
+// being reflected in another. the rows (or columns) repeat on both sides of
+// both lines, and then a tile only the second line reflects changes. the rows
+// on either side of any other line differ in at least two tiles
+fn day13(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for pattern in 0..size {
+        if pattern > 0 {
+            out.write_char('\n')?;
+        }
+        let (rows, width) = loop {
+            let gap = rng.range(1, 8) as usize;
+            let first = rng.range(1, gap as i64 + 1) as usize;
+            let second = first + gap;
+            let len = rng.range(second as i64 + 1, (first + 2 * gap) as i64 + 1) as usize;
+            let width = rng.range(5, 18) as usize;
+            let mut kinds = [0u32; 7];
+            for i in 0..gap {
+                kinds[i] = loop {
+                    let kind = rng.below(1 << width) as u32;
+                    if kinds[..i].iter().all(|other| (kind ^ other).count_ones() >= 2) {
+                        break kind;
+                    }
+                };
+            }
+            let mut rows: Vec<u32, 32> = (0..len)
+                .map(|i| (i + 2 * gap - first) % (2 * gap))
+                .map(|t| kinds[if t < gap { t } else { 2 * gap - 1 - t }])
+                .collect();
+            let smudge = rng.range((2 * first).max(2 * second - len) as i64, len as i64) as usize;
+            rows[smudge] ^= 1 << rng.below(width as u64);
+            if (1..width).all(|line| column_diffs(&rows, width, line) >= 2) {
+                break (rows, width);
+            }
+        };
+        let transpose = rng.below(2) == 0;
+        let (height, across) = if transpose { (width, rows.len()) } else { (rows.len(), width) };
+        for r in 0..height {
+            for c in 0..across {
+                let ash = if transpose { rows[c] >> r & 1 } else { rows[r] >> c & 1 } == 0;
+                out.write_char(if ash { '.' } else { '#' })?;
+            }
+            out.write_char('\n')?;
+        }
+    }
+    Ok(())
+}
+
+// a `size` by `size` platform (at most 128) with a rounded rock on about one
+// tile in five and a cube-shaped one on one in six, like the real input
+fn day14(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size.min(128) {
+        for _ in 0..size.min(128) {
+            out.write_char(match rng.below(30) {
+                0..=5 => 'O',
+                6..=10 => '#',
+                _ => '.',
+            })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` steps (at most 2048, so the lenses fit the solver's memory) on a
+// quarter as many labels of two to six letters, each step as likely to insert
+// or replace a lens as to remove one
+fn day15(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let (seed, labels) = (rng.next_u64(), size as u64 / 4 + 1);
+    for step in 0..size.min(2048) {
+        if step > 0 {
+            out.write_char(',')?;
+        }
+        let mut label = Rng::new(seed.wrapping_add(rng.below(labels)));
+        for _ in 0..label.range(2, 7) {
+            out.write_char(letter(&mut label))?;
+        }
+        if rng.below(2) == 0 {
+            write!(out, "={}", digit(rng))?;
+        } else {
+            out.write_char('-')?;
+        }
+    }
+    out.write_char('\n')
+}
+
+// a `size` by `size` contraption (at most 110) with a mirror or a splitter on
+// about one tile in ten, like the real input
+fn day16(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size.min(110) {
+        for _ in 0..size.min(110) {
+            out.write_char(match rng.below(40) {
+                0 => '/',
+                1 => '\\',
+                2 => '|',
+                3 => '-',
+                _ => '.',
+            })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// a `size` by `size` map (at least 5, so an ultra crucible can reach the end)
+// of heat losses from 1 to 9
+fn day17(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    for _ in 0..size.max(5) {
+        for _ in 0..size.max(5) {
+            out.write_char(digit(rng))?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// about `size` instructions (at most 1000) that dig around a random polygon
+// (see `polygon`), and the same polygon in the colors, with its corners
+// spread much further apart
+fn day18(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let width = (size / 4).clamp(2, 250);
+    let corners = polygon(rng, width, width);
+    // where each row and column of the polygon's grid ends up
+    let far = 0xfffff / (2 * width as i64);
+    let mut coords: [Vec<i64, 256>; 4] = Default::default();
+    for (axis, coords) in coords.iter_mut().enumerate() {
+        let max_gap = if axis < 2 { 10 } else { far };
+        let mut at = 0;
+        for _ in 0..=width {
+            coords.push(at).unwrap();
+            at += rng.range(1, max_gap + 1);
+        }
+    }
+    let [rows, cols, far_rows, far_cols] = &coords;
+    for (i, &(row, col)) in corners.iter().enumerate() {
+        let (next_row, next_col) = corners[(i + 1) % corners.len()];
+        let (dir, code) = match (next_row.cmp(&row), next_col.cmp(&col)) {
+            (_, core::cmp::Ordering::Greater) => ('R', 0),
+            (core::cmp::Ordering::Greater, _) => ('D', 1),
+            (_, core::cmp::Ordering::Less) => ('L', 2),
+            _ => ('U', 3),
+        };
+        let dist = |rows: &[i64], cols: &[i64]| {
+            (rows[next_row] - rows[row]).abs() + (cols[next_col] - cols[col]).abs()
+        };
+        let (near, far) = (dist(rows, cols), dist(far_rows, far_cols));
+        writeln!(out, "{} {} (#{:05x}{})", dir, near, far, code)?;
+    }
+    Ok(())
+}
+
+// `in` for the first, and three letters for the rest
+fn day19_label(out: &mut dyn Write, i: usize) -> fmt::Result {
+    if i == 0 {
+        return out.write_str("in");
+    }
+    for div in [676, 26, 1] {
+        out.write_char((b'a' + (i / div % 26) as u8) as char)?;
+    }
+    Ok(())
+}
+
+// a tree of about `size` workflows (at most 200, so the solver has room for
+// the ranges that are accepted) of up to four rules, from `in` down to
+// accepting or rejecting, and `size` parts to sort
+fn day19(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let size = size.clamp(1, 200);
+    // a new workflow, to be written after the ones before it, or the end
+    let target = |rng: &mut Rng, out: &mut dyn Write, created: &mut usize| {
+        if *created < size && rng.below(2) == 0 {
+            *created += 1;
+            day19_label(out, *created - 1)
+        } else {
+            out.write_char(rng.pick(&['A', 'R']))
+        }
+    };
+    let (mut workflow, mut created) = (0, 1);
+    while workflow < created {
+        day19_label(out, workflow)?;
+        out.write_char('{')?;
+        for _ in 0..rng.range(1, 5) {
+            let (var, op) = (rng.pick(&['x', 'm', 'a', 's']), rng.pick(&['<', '>']));
+            write!(out, "{}{}{}:", var, op, rng.range(1, 4001))?;
+            target(rng, out, &mut created)?;
+            out.write_char(',')?;
+        }
+        target(rng, out, &mut created)?;
+        out.write_str("}\n")?;
+        workflow += 1;
+    }
+    out.write_char('\n')?;
+    for _ in 0..size {
+        let mut part = || rng.range(1, 4001);
+        writeln!(out, "{{x={},m={},a={},s={}}}", part(), part(), part(), part())?;
+    }
+    Ok(())
+}
+
+// up to four counters of `size` flip-flops each (at most 12), wired like the
+// real input's: a conjunction watches the flip-flops that are on in a prime,
+// and when the button presses reach it, sends a low pulse (through an
+// inverter and another conjunction that joins the counters) to `rx`, and
+// resets the counter. the primes have few enough bits off that the
+// conjunction has room for an output to each of their flip-flops
+fn day20(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let bits = size.clamp(2, 12);
+    let is_counted = |n: &usize| is_prime(*n) && bits - n.count_ones() as usize <= 6;
+    let counted = (1 << (bits - 1)..1 << bits).filter(is_counted);
+    let mut primes: Vec<usize, 4> = Vec::new();
+    while primes.len() < rng.range(1, 5).min(counted.clone().count() as i64) as usize {
+        let prime = counted.clone().nth(rng.below(counted.clone().count() as u64) as usize);
+        if !primes.contains(&prime.unwrap()) {
+            primes.push(prime.unwrap()).unwrap();
+        }
+    }
+    let name = |i: usize| [b'a' + (i / 26) as u8, b'a' + (i % 26) as u8];
+    let names = |ids: &[usize], out: &mut dyn Write| -> fmt::Result {
+        for (i, &id) in ids.iter().enumerate() {
+            let name = name(id);
+            write!(
+                out,
+                "{}{}",
+                if i > 0 { ", " } else { "" },
+                core::str::from_utf8(&name).unwrap()
+            )?;
+        }
+        out.write_char('\n')
+    };
+    // each counter's flip-flops, then its conjunction and inverter
+    let id = |counter: usize, i: usize| counter * (bits + 2) + i;
+    let sink = primes.len() * (bits + 2);
+    out.write_str("broadcaster -> ")?;
+    names(&(0..primes.len()).map(|counter| id(counter, 0)).collect::<Vec<usize, 4>>(), out)?;
+    for (counter, &prime) in primes.iter().enumerate() {
+        let (conj, inverter) = (id(counter, bits), id(counter, bits + 1));
+        for bit in 0..bits {
+            let mut outputs: Vec<usize, 2> = Vec::new();
+            if bit + 1 < bits {
+                outputs.push(id(counter, bit + 1)).unwrap();
+            }
+            if prime >> bit & 1 == 1 {
+                outputs.push(conj).unwrap();
+            }
+            write!(out, "%{} -> ", core::str::from_utf8(&name(id(counter, bit))).unwrap())?;
+            names(&outputs, out)?;
+        }
+        let mut outputs: Vec<usize, 8> = Vec::new();
+        outputs.push(id(counter, 0)).unwrap();
+        for bit in (0..bits).filter(|bit| prime >> bit & 1 == 0) {
+            outputs.push(id(counter, bit)).unwrap();
+        }
+        outputs.push(inverter).unwrap();
+        write!(out, "&{} -> ", core::str::from_utf8(&name(conj)).unwrap())?;
+        names(&outputs, out)?;
+        write!(out, "&{} -> ", core::str::from_utf8(&name(inverter)).unwrap())?;
+        names(&[sink], out)?;
+    }
+    writeln!(out, "&{} -> rx", core::str::from_utf8(&name(sink)).unwrap())
+}
+
+// a `size` by `size` garden (an odd size, at least 5, which has to be 131 for
+// the puzzle's number of steps in part 2) with the start in the middle and a
+// rock on about one plot in eight, except in the start's row and column and on
+// the diamond between the middles of the edges, like the real input
+fn day21(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let side = size.max(5) | 1;
+    let half = side / 2;
+    for row in 0..side {
+        for col in 0..side {
+            let open =
+                row == half || col == half || row.abs_diff(half) + col.abs_diff(half) == half;
+            out.write_char(match () {
+                _ if (row, col) == (half, half) => 'S',
+                _ if open || rng.below(8) != 0 => '.',
+                _ => '#',
+            })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` bricks of up to four cubes over a 10x10 footprint, each starting
+// above the last so that none overlap
+fn day22(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let mut z = 1;
+    for _ in 0..size {
+        let len = rng.range(0, 4);
+        let (x, y) = (rng.range(0, 10), rng.range(0, 10));
+        let (dx, dy, dz) = match rng.below(3) {
+            0 => (len.min(9 - x), 0, 0),
+            1 => (0, len.min(9 - y), 0),
+            _ => (0, 0, len),
+        };
+        writeln!(out, "{},{},{}~{},{},{}", x, y, z, x + dx, y + dy, z + dz)?;
+        z += dz + 1;
+    }
+    Ok(())
+}
+
+// a `size` by `size` grid of junctions (at most 6, as in the real input, which
+// is as many as the longest path search has time for), joined to the ones
+// right of and below them by corridors with a slope down or right at each
+// end, from the start to the top left one and from the bottom right one to
+// the end
+fn day23(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let junctions = size.clamp(1, 6);
+    let (mut rows, mut cols) = ([rng.range(2, 6) as usize; 6], [1; 6]);
+    for i in 1..junctions {
+        rows[i] = rows[i - 1] + rng.range(4, 11) as usize;
+        cols[i] = cols[i - 1] + rng.range(4, 11) as usize;
+    }
+    let (rows, cols) = (&rows[..junctions], &cols[..junctions]);
+    let (last_row, last_col) = (rows[junctions - 1], cols[junctions - 1]);
+    let (height, width) = (last_row + rng.range(3, 7) as usize, last_col + 2);
+    // the tile at `at` along a row or column of junctions at `ends`
+    let corridor = |ends: &[usize], at: usize, slope| match ends.iter().position(|end| *end > at) {
+        Some(i) if i > 0 && (at == ends[i - 1] + 1 || at + 1 == ends[i]) => slope,
+        Some(i) if i > 0 => '.',
+        _ => '#',
+    };
+    for row in 0..height {
+        for col in 0..width {
+            let (junction_row, junction_col) = (rows.contains(&row), cols.contains(&col));
+            out.write_char(match () {
+                _ if junction_row && junction_col => '.',
+                _ if junction_row => corridor(cols, col, '>'),
+                _ if col == 1 && row < rows[0] => {
+                    if row + 1 == rows[0] {
+                        'v'
+                    } else {
+                        '.'
+                    }
+                }
+                _ if col == last_col && row > last_row => {
+                    if row == last_row + 1 {
+                        'v'
+                    } else {
+                        '.'
+                    }
+                }
+                _ if junction_col => corridor(rows, row, 'v'),
+                _ => '#',
+            })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+// `size` hailstones that a rock thrown from the middle of part 1's test area
+// hits, at random times, so that they start around the test area too
+fn day24(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let rock: [i64; 3] = core::array::from_fn(|_| rng.range(200000000000000, 400000000000000));
+    let rock_vel: [i64; 3] = core::array::from_fn(|_| rng.range(-300, 300));
+    for _ in 0..size {
+        let t = rng.range(10000000000, 200000000000);
+        let vel: [i64; 3] = core::array::from_fn(|_| rng.range(-500, 500));
+        let pos: [i64; 3] = core::array::from_fn(|i| rock[i] + (rock_vel[i] - vel[i]) * t);
+        let [x, y, z] = pos;
+        let [dx, dy, dz] = vel;
+        writeln!(out, "{}, {}, {} @ {}, {}, {}", x, y, z, dx, dy, dz)?;
+    }
+    Ok(())
+}
+
+// `size` components (at least 10) in two groups, each a ring where every
+// component is also wired to the next but one, and now and then to another,
+// so that it takes four cuts to split, and three wires between the groups
+fn day25(rng: &mut Rng, size: usize, out: &mut dyn Write) -> fmt::Result {
+    let size = size.clamp(10, 17576);
+    let first = rng.range(5, size as i64 - 4) as usize;
+    let mut ends: [usize; 3] = [0; 3];
+    while ends[1..].contains(&ends[0]) || ends[1] == ends[2] {
+        ends = core::array::from_fn(|_| rng.below(first as u64) as usize);
+    }
+    // scattered over all the names of three letters
+    let name = |i: usize| {
+        let i = i * 7919 % 17576;
+        [b'a' + (i / 676) as u8, b'a' + (i / 26 % 26) as u8, b'a' + (i % 26) as u8]
+    };
+    let write =
+        |out: &mut dyn Write, i: usize| out.write_str(core::str::from_utf8(&name(i)).unwrap());
+    for i in 0..size {
+        let (lo, len) = if i < first { (0, first) } else { (first, size - first) };
+        write(out, i)?;
+        out.write_char(':')?;
+        let chord = len > 5 && rng.below(4) == 0;
+        let chord = chord.then(|| rng.range(3, len as i64 - 2) as usize);
+        for j in [1, 2].into_iter().chain(chord) {
+            let next = i - lo + j;
+            // the other way around the ring is the other end's
+            if j < 3 || next < len {
+                out.write_char(' ')?;
+                write(out, lo + next % len)?;
+            }
+        }
+        if ends.contains(&i) {
+            out.write_char(' ')?;
+            write(out, first + rng.below((size - first) as u64) as usize)?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+    use heapless::String;
+
+    #[test]
+    fn test_generators() {
+        let mut rng = Rng::new(1);
+        let mut input: String<65536> = String::new();
+        day11(&mut rng, 100, &mut input).unwrap();
+        let galaxies = input.bytes().filter(|b| *b == b'#').count();
+        assert!((50..150).contains(&galaxies), "{} galaxies", galaxies);
+        assert!(day11::part1(&day11::parse(&input)) > 0);
+
+        input.clear();
+        day22(&mut rng, 100, &mut input).unwrap();
+        assert_eq!(input.lines().count(), 100);
+        assert!(day22::part1(&day22::parse(&input)) > 0);
+
+        input.clear();
+        day24(&mut rng, 100, &mut input).unwrap();
+        assert_eq!(input.lines().count(), 100);
+        assert!(day24::part1(&day24::parse(&input)) > 0);
+    }
+
+    // both parts of the days the library solves, from one parse as the binary
+    // solves them
+    fn solve(day: u8, input: &str) -> Option<[i64; 2]> {
+        macro_rules! parts {
+            // in a function of its own, or unoptimized builds make one frame
+            // with room for every day's parsed input
+            ($day:ident) => {{
+                fn parts(input: &str) -> [i64; 2] {
+                    let parsed = $day::parse(input);
+                    [$day::part1(&parsed) as i64, $day::part2(&parsed) as i64]
+                }
+                parts(input)
+            }};
+        }
+        Some(match day {
+            1 => parts!(day1),
+            2 => parts!(day2),
+            3 => parts!(day3),
+            4 => parts!(day4),
+            5 => parts!(day5),
+            6 => parts!(day6),
+            7 => parts!(day7),
+            8 => parts!(day8),
+            9 => parts!(day9),
+            10 => parts!(day10),
+            11 => parts!(day11),
+            12 => parts!(day12),
+            13 => parts!(day13),
+            14 => parts!(day14),
+            15 => parts!(day15),
+            16 => parts!(day16),
+            17 => parts!(day17),
+            18 => parts!(day18),
+            19 => parts!(day19),
+            20 => parts!(day20),
+            22 => parts!(day22),
+            23 => parts!(day23),
+            24 => [day24::part1(&day24::parse(input)) as i64, 0],
+            _ => return None,
+        })
+    }
+
+    // every day solves small inputs and the largest it has room for from a
+    // few seeds without panicking, and the variants agree on them
+    #[test]
+    fn test_days() {
+        let mut input: String<{ 1 << 17 }> = String::new();
+        for generator in GENERATORS {
+            let max = match (generator.day, generator.max_size) {
+                // the binary tests the days it solves at their largest
+                (21 | 25, _) => 20,
+                (_, max) => max,
+            };
+            // the longest path search takes too long on bigger grids unoptimized
+            let small = if generator.day == 23 { 4 } else { max };
+            for seed in 0..8 {
+                // and so do the largest inputs, so they get fewer seeds
+                let largest = (seed < 3).then_some(max);
+                for size in [1, 3, 8, 20].map(|size| size.min(small)).into_iter().chain(largest) {
+                    input.clear();
+                    (generator.generate)(&mut Rng::new(seed), size, &mut input).unwrap();
+                    let Some(answers) = solve(generator.day, &input) else { continue };
+                    for (part, answer) in (1..).zip(answers) {
+                        for variant in variants::variants(generator.day, part) {
+                            let got = (variant.solve)(&input);
+                            assert_eq!(
+                                got, answer,
+                                "day{} {}\n{}",
+                                generator.day, variant.name, input
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
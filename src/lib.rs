@@ -22,11 +22,12 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
+pub mod gen;
 mod lines;
+pub mod params;
 pub mod static_map;
 pub mod static_queue;
 pub mod static_treemap;
 pub mod static_vec;
-
-#[macro_use]
-extern crate lazy_static;
+pub mod variants;
+pub mod viz;
@@ -1,13 +1,11 @@
-use core::str::Lines;
-
 pub type LineWindow<'a> = (Option<&'a str>, &'a str, Option<&'a str>);
 
-pub struct Windows<'a> {
-    lines: Lines<'a>,
+pub struct Windows<'a, I> {
+    lines: I,
     buf: [Option<&'a str>; 2],
 }
 
-impl<'a> Iterator for Windows<'a> {
+impl<'a, I: Iterator<Item = &'a str>> Iterator for Windows<'a, I> {
     type Item = LineWindow<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -17,8 +15,10 @@ impl<'a> Iterator for Windows<'a> {
     }
 }
 
-pub fn windows(input: &str) -> Windows {
-    let mut lines = input.lines();
+pub fn windows<'a, I: Iterator<Item = &'a str>>(
+    lines: impl IntoIterator<IntoIter = I>,
+) -> Windows<'a, I> {
+    let mut lines = lines.into_iter();
     let buf = [None, lines.next()];
     Windows { lines, buf }
 }
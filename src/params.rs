@@ -0,0 +1,41 @@
+// the constants that come from a puzzle's text rather than its input, like
+// day 24's test area. the examples often use other values, so each day that
+// has any keeps them in a `Params` struct whose default is the puzzle's, and
+// the cli can set them by name
+use core::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Unknown,
+    Invalid,
+}
+
+pub trait Params: Default {
+    // the names that `set` takes
+    const NAMES: &'static [&'static str];
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), Error>;
+}
+
+pub fn parse<T: FromStr>(value: &str) -> Result<T, Error> {
+    value.trim().parse().map_err(|_| Error::Invalid)
+}
+
+// `lo..hi`, for the parameters that are a range
+pub fn parse_range<T: FromStr>(value: &str) -> Result<(T, T), Error> {
+    let (lo, hi) = value.split_once("..").ok_or(Error::Invalid)?;
+    Ok((parse(lo)?, parse(hi)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse::<i64>(" 12"), Ok(12));
+        assert_eq!(parse::<usize>("-1"), Err(Error::Invalid));
+        assert_eq!(parse_range::<i128>("7..27"), Ok((7, 27)));
+        assert_eq!(parse_range::<i128>("7-27"), Err(Error::Invalid));
+    }
+}
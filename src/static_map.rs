@@ -1,4 +1,5 @@
 use crate::static_vec::StaticVec;
+#[allow(deprecated)]
 use core::hash::{Hash, Hasher, SipHasher};
 
 pub struct StaticMap<K, V, const NUM_BUCKETS: usize, const BUCKET_SIZE: usize>
@@ -52,6 +53,7 @@ where
     }
 
     fn bucket_id(key: &K) -> usize {
+        #[allow(deprecated)]
         let mut hasher = SipHasher::new();
         key.hash(&mut hasher);
         hasher.finish() as usize % NUM_BUCKETS
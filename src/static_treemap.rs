@@ -48,7 +48,7 @@ where
     }
 
     pub fn is_empty(&self) -> bool {
-        self.root == None
+        self.root.is_none()
     }
 
     fn alloc(&mut self, key: K, value: V) -> Option<u16> {
@@ -150,6 +150,16 @@ where
     }
 }
 
+impl<K, V, const N: usize> Default for StaticTreeMap<K, V, N>
+where
+    K: Clone + Copy + Default + Eq + Ord,
+    V: Clone + Copy + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct StaticTreeSet<K: Clone + Copy + Default + Ord + Eq, const N: usize>(
     StaticTreeMap<K, bool, N>,
 );
@@ -183,3 +193,9 @@ impl<K: Clone + Copy + Default + Ord + Eq, const N: usize> StaticTreeSet<K, N> {
         self.0.iter().map(|(k, _)| k)
     }
 }
+
+impl<K: Clone + Copy + Default + Ord + Eq, const N: usize> Default for StaticTreeSet<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
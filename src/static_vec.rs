@@ -61,8 +61,8 @@ impl<T: Default + Copy, const N: usize> StaticVec<T, N> {
         self.data[..self.len].iter_mut()
     }
 
-    pub fn sort_by(&mut self, cmp: impl FnMut(&T, &T) -> Ordering) {
-        self.data[..self.len].sort_by(cmp);
+    pub fn sort_unstable_by(&mut self, cmp: impl FnMut(&T, &T) -> Ordering) {
+        self.data[..self.len].sort_unstable_by(cmp);
     }
 
     pub fn binary_search_by_key<K: Ord>(&self, t: &K, f: impl Fn(&T) -> K) -> Option<usize> {
@@ -0,0 +1,169 @@
+// the parts that can be solved more than one way, each way under a name, so
+// that the cli can pick one, the benches can time them side by side and the
+// tests can check that they all agree
+use crate::{day10, day23};
+
+pub struct Variant {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&str) -> i64,
+}
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        day: 10,
+        part: 2,
+        name: "flood",
+        solve: |input| day10::part2(&day10::parse(input)) as i64,
+    },
+    Variant {
+        day: 10,
+        part: 2,
+        name: "shoelace",
+        solve: |input| day10::part2_shoelace(&day10::parse(input)) as i64,
+    },
+    Variant {
+        day: 23,
+        part: 1,
+        name: "pruned",
+        solve: |input| day23::part1(&day23::parse(input)) as i64,
+    },
+    Variant {
+        day: 23,
+        part: 1,
+        name: "unbounded",
+        solve: |input| day23::part1_unbounded(&day23::parse(input)) as i64,
+    },
+    Variant {
+        day: 23,
+        part: 2,
+        name: "pruned",
+        solve: |input| day23::part2(&day23::parse(input)) as i64,
+    },
+    Variant {
+        day: 23,
+        part: 2,
+        name: "unbounded",
+        solve: |input| day23::part2_unbounded(&day23::parse(input)) as i64,
+    },
+];
+
+pub fn variants(day: u8, part: u8) -> impl Iterator<Item = &'static Variant> {
+    VARIANTS.iter().filter(move |v| v.day == day && v.part == part)
+}
+
+pub fn find(day: u8, part: u8, name: &str) -> Option<&'static Variant> {
+    variants(day, part).find(|v| v.name == name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // the inputs of the parts that have variants, examples first
+    const INPUTS: &[(u8, &str)] = &[
+        (
+            10,
+            "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+",
+        ),
+        (
+            10,
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+",
+        ),
+        (
+            10,
+            ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+",
+        ),
+        (
+            10,
+            "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+",
+        ),
+        (10, include_str!("../inputs/day10.txt")),
+        (
+            23,
+            "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+",
+        ),
+        (23, include_str!("../inputs/day23.txt")),
+    ];
+
+    #[test]
+    fn test_variants_agree() {
+        for &(day, input) in INPUTS {
+            for part in 1..=2 {
+                let mut answers = variants(day, part).map(|v| (v.name, (v.solve)(input)));
+                let Some((first, want)) = answers.next() else { continue };
+                for (name, got) in answers {
+                    assert_eq!(got, want, "day {} part {}: {} vs {}", day, part, name, first);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(10, 2, "shoelace").map(|v| v.name), Some("shoelace"));
+        assert!(find(10, 1, "shoelace").is_none());
+    }
+}
@@ -0,0 +1,20 @@
+// Hooks for watching a solver work through a grid. Instrumented solvers take
+// an `&mut impl Observer<Cell>` for a day-specific cell type; the plain
+// part1/part2 entry points pass `&mut ()`, for which every hook is a no-op.
+
+pub type Pos = (usize, usize);
+
+pub trait Observer<C> {
+    // the cell at (row, col) should now be drawn as `cell`
+    fn cell_changed(&mut self, _pos: Pos, _cell: C) {}
+
+    // the changes since the previous frame belong together
+    fn frame(&mut self) {}
+
+    // the solver should give up at the next frame, returning whatever it has
+    fn stopped(&self) -> bool {
+        false
+    }
+}
+
+impl<C> Observer<C> for () {}